@@ -3,6 +3,7 @@
 #![allow(non_upper_case_globals)]
 
 mod util_macros;
+use dracula::count::CountPolicy;
 use std::ffi::{self, c_char};
 
 languages_supported! {
@@ -10,6 +11,24 @@ languages_supported! {
     const C = 2;
     const Rust = 3;
     const Java = 4;
+    const Go = 5;
+    const Kotlin = 6;
+    const Cpp = 7;
+    const Swift = 8;
+    const Php = 9;
+    const Shell = 10;
+    const Lua = 11;
+    const Sql = 12;
+    const Postgres = 13;
+    const MySql = 14;
+    const TSql = 15;
+    const Haskell = 16;
+    const Dart = 17;
+    const Elixir = 18;
+    const Erlang = 19;
+    const Perl = 20;
+    const Julia = 21;
+    const R = 22;
 }
 
 #[no_mangle]
@@ -20,23 +39,85 @@ pub static C_LANG: ffi::c_uint = C;
 pub static RUST_LANG: ffi::c_uint = Rust;
 #[no_mangle]
 pub static JAVA_LANG: ffi::c_uint = Java;
+#[no_mangle]
+pub static GO_LANG: ffi::c_uint = Go;
+#[no_mangle]
+pub static KOTLIN_LANG: ffi::c_uint = Kotlin;
+#[no_mangle]
+pub static CPP_LANG: ffi::c_uint = Cpp;
+#[no_mangle]
+pub static SWIFT_LANG: ffi::c_uint = Swift;
+#[no_mangle]
+pub static PHP_LANG: ffi::c_uint = Php;
+#[no_mangle]
+pub static SHELL_LANG: ffi::c_uint = Shell;
+#[no_mangle]
+pub static LUA_LANG: ffi::c_uint = Lua;
+#[no_mangle]
+pub static SQL_LANG: ffi::c_uint = Sql;
+#[no_mangle]
+pub static POSTGRES_LANG: ffi::c_uint = Postgres;
+#[no_mangle]
+pub static MYSQL_LANG: ffi::c_uint = MySql;
+#[no_mangle]
+pub static TSQL_LANG: ffi::c_uint = TSql;
+#[no_mangle]
+pub static HASKELL_LANG: ffi::c_uint = Haskell;
+#[no_mangle]
+pub static DART_LANG: ffi::c_uint = Dart;
+#[no_mangle]
+pub static ELIXIR_LANG: ffi::c_uint = Elixir;
+#[no_mangle]
+pub static ERLANG_LANG: ffi::c_uint = Erlang;
+#[no_mangle]
+pub static PERL_LANG: ffi::c_uint = Perl;
+#[no_mangle]
+pub static JULIA_LANG: ffi::c_uint = Julia;
+#[no_mangle]
+pub static R_LANG: ffi::c_uint = R;
+
+const Meaningful: ffi::c_uint = 0;
+const PhysicalSloc: ffi::c_uint = 1;
+const Executable: ffi::c_uint = 2;
+const Review: ffi::c_uint = 3;
+
+/// `kind` for [`CountPolicy::MEANINGFUL`], the default
+#[no_mangle]
+pub static MEANINGFUL_KIND: ffi::c_uint = Meaningful;
+/// `kind` for [`CountPolicy::PHYSICAL_SLOC`]
+#[no_mangle]
+pub static PHYSICAL_SLOC_KIND: ffi::c_uint = PhysicalSloc;
+/// `kind` for [`CountPolicy::EXECUTABLE`]
+#[no_mangle]
+pub static EXECUTABLE_KIND: ffi::c_uint = Executable;
+/// `kind` for [`CountPolicy::REVIEW`]
+#[no_mangle]
+pub static REVIEW_KIND: ffi::c_uint = Review;
+
+/// Unknown kinds fallback to the default policy
+fn count_policy(kind: ffi::c_uint) -> CountPolicy {
+    match kind {
+        PhysicalSloc => CountPolicy::PHYSICAL_SLOC,
+        Executable => CountPolicy::EXECUTABLE,
+        Review => CountPolicy::REVIEW,
+        _ => CountPolicy::MEANINGFUL,
+    }
+}
 
 #[no_mangle]
 /// This function is used to get the count of meaningful lines in the source.
 ///
-/// It currently doesn't support setting the multiple ways(`kind`) of meaningful line
-/// search eg. `ignore whitespace`, `specific character`, etc.
-/// aka the definition of a meaningful line.
-/// But provided as field to avoid ABI incompatibility later.
+/// The definition of a meaningful line is picked with `kind`, one of `MEANINGFUL_KIND`,
+/// `PHYSICAL_SLOC_KIND`, `EXECUTABLE_KIND` or `REVIEW_KIND`.
 pub unsafe fn get_meaningful_line_count(
     src: *const c_char,
     lang: ffi::c_uint,
-    _kind: ffi::c_uint,
+    kind: ffi::c_uint,
 ) -> ffi::c_ulonglong {
     let cstr = ffi::CStr::from_ptr(src);
     cstr.to_str()
         .ok()
-        .map(|src| get_count_of_meaningful_lines_as_u64(lang, src))
+        .map(|src| get_count_of_meaningful_lines_as_u64(lang, src, count_policy(kind)))
         .flatten()
         .unwrap_or_default() as _
 }
@@ -44,17 +125,15 @@ pub unsafe fn get_meaningful_line_count(
 #[no_mangle]
 /// This function is used to get the list of meaningful lines in the source.
 ///
-/// It currently doesn't support setting the multiple ways(`kind`) of meaningful line
-/// search eg. `ignore whitespace`, `specific character`, etc.
-/// aka the definition of a meaningful line.
-/// But provided as field to avoid ABI incompatibility later.
+/// The definition of a meaningful line is picked with `kind`, one of `MEANINGFUL_KIND`,
+/// `PHYSICAL_SLOC_KIND`, `EXECUTABLE_KIND` or `REVIEW_KIND`.
 ///
 /// NOTE:
 /// The caller is responsible for free'ing the obtained array
 pub unsafe fn meaningful_lines(
     src: *const c_char,
     lang: ffi::c_uint,
-    _kind: ffi::c_uint,
+    kind: ffi::c_uint,
     r_lines_len: *mut ffi::c_ulonglong,
 ) -> *mut ffi::c_ulonglong {
     if cfg!(dbg) {
@@ -64,7 +143,7 @@ pub unsafe fn meaningful_lines(
     let mut meaningful_lines = cstr
         .to_str()
         .ok()
-        .and_then(|src| get_meaningful_line_indices_as_u64(lang, src))
+        .and_then(|src| get_meaningful_line_indices_as_u64(lang, src, count_policy(kind)))
         .unwrap_or_else(|| vec![]);
     meaningful_lines.shrink_to_fit();
     if cfg!(dbg) {
@@ -81,24 +160,22 @@ pub unsafe fn meaningful_lines(
 /// This function is used to get the source of just the meaningful parts in the source,
 /// including the whitespaces.
 ///
-/// It currently doesn't support setting the multiple ways(`kind`) of meaningful line
-/// search eg. `ignore whitespace`, `specific character`, etc.
-/// aka the definition of a meaningful line.
-/// But provided as field to avoid ABI incompatibility later.
+/// The definition of a meaningful line is picked with `kind`, one of `MEANINGFUL_KIND`,
+/// `PHYSICAL_SLOC_KIND`, `EXECUTABLE_KIND` or `REVIEW_KIND`.
 ///
 /// NOTE:
 /// The caller is responsible for free'ing the obtained array
 pub unsafe fn get_cleaned_src(
     src: *const c_char,
     lang: ffi::c_uint,
-    _kind: ffi::c_uint,
+    kind: ffi::c_uint,
     _exclude: ffi::c_uint,
 ) -> *mut i8 {
     let cstr = ffi::CStr::from_ptr(src);
     let src = cstr
         .to_str()
         .ok()
-        .map(|src| get_cleaned_source_code(lang, src))
+        .map(|src| get_cleaned_source_code(lang, src, count_policy(kind)))
         .flatten()
         .unwrap_or_default();
     ffi::CString::from_vec_unchecked(src.into()).into_raw()
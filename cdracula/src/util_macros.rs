@@ -4,11 +4,16 @@ macro_rules! languages_supported {
         $(
             const $name: std::ffi::c_uint = $num;
         )+
-        pub fn get_meaningful_line_indices_as_u64(idx: std::ffi::c_uint, src: &str) -> Option<Vec<u64>> {
+        pub fn get_meaningful_line_indices_as_u64(
+            idx: std::ffi::c_uint,
+            src: &str,
+            policy: dracula::count::CountPolicy,
+        ) -> Option<Vec<u64>> {
             $(
                 if idx == $num {
                     return Some(
                         dracula::count::get_meaningful_line_indices::<dracula::langs::$name>(src)
+                            .with_policy(policy)
                             .flatten()
                             .map(|x| x as u64)
                             .collect()
@@ -17,21 +22,29 @@ macro_rules! languages_supported {
             )+
             None
         }
-        pub fn get_cleaned_source_code(idx: std::ffi::c_uint, src: &str) -> Option<String> {
+        pub fn get_cleaned_source_code(
+            idx: std::ffi::c_uint,
+            src: &str,
+            policy: dracula::count::CountPolicy,
+        ) -> Option<String> {
             $(
                 if idx == $num {
                     return Some(
-                        dracula::count::get_cleaned_source_code::<dracula::langs::$name>(src).unwrap_or_else(|| src.to_string())
+                        dracula::count::get_cleaned_source_code_with_policy::<dracula::langs::$name>(src, policy).unwrap_or_else(|| src.to_string())
                     );
                 }
             )+
             None
         }
-        pub fn get_count_of_meaningful_lines_as_u64(idx: std::ffi::c_uint, src: &str) -> Option<u64> {
+        pub fn get_count_of_meaningful_lines_as_u64(
+            idx: std::ffi::c_uint,
+            src: &str,
+            policy: dracula::count::CountPolicy,
+        ) -> Option<u64> {
             $(
                 if idx == $num {
                     return Some(
-                        dracula::count::get_count_of_meaningful_lines::<dracula::langs::$name>(src) as _
+                        dracula::count::get_count_of_meaningful_lines_with_policy::<dracula::langs::$name>(src, policy) as _
                     );
                 }
             )+
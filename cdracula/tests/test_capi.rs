@@ -6,6 +6,13 @@ extern "C" {
     static PYTHON_LANG: ffi::c_uint;
     static RUST_LANG: ffi::c_uint;
     static JAVA_LANG: ffi::c_uint;
+    static GO_LANG: ffi::c_uint;
+    static KOTLIN_LANG: ffi::c_uint;
+    static CPP_LANG: ffi::c_uint;
+    static MEANINGFUL_KIND: ffi::c_uint;
+    static PHYSICAL_SLOC_KIND: ffi::c_uint;
+    static EXECUTABLE_KIND: ffi::c_uint;
+    static REVIEW_KIND: ffi::c_uint;
     fn get_meaningful_line_count(
         src: *const ffi::c_char,
         lang: ffi::c_uint,
@@ -143,6 +150,49 @@ int main() {
         }
     }
 
+    #[test]
+    fn test_get_meaningful_line_count_cpp() {
+        unsafe {
+            let src = CString::from_vec_unchecked(
+                (String::from(
+                    r#"
+auto s = R"-(
+    )"
+)-";
+long n = 1'000; // '
+"#,
+                ) + "\0")
+                    .into(),
+            );
+            assert_eq!(get_meaningful_line_count(src.as_ptr(), CPP_LANG, 0), 2);
+        }
+    }
+
+    #[test]
+    fn test_get_meaningful_line_count_with_kind() {
+        unsafe {
+            let src = CString::from_vec_unchecked(
+                (String::from(
+                    r#"
+#include <stdio.h>
+// interesting line
+int main() {
+    char* s = "multi \
+        line";
+    return 0;
+}
+"#,
+                ) + "\0")
+                    .into(),
+            );
+            let count = |kind| get_meaningful_line_count(src.as_ptr(), C_LANG, kind);
+            assert_eq!(count(MEANINGFUL_KIND), 4);
+            assert_eq!(count(PHYSICAL_SLOC_KIND), 6);
+            assert_eq!(count(EXECUTABLE_KIND), 3);
+            assert_eq!(count(REVIEW_KIND), 5);
+        }
+    }
+
     #[test]
     fn test_get_meaningful_lines() {
         unsafe {
@@ -198,3 +248,57 @@ r#"int main() {
         }
     }
 }
+
+#[cfg(test)]
+mod go {
+    use super::*;
+    use std::ffi::CString;
+
+    #[test]
+    fn test_get_meaningful_line_count() {
+        unsafe {
+            let src = CString::from_vec_unchecked(
+                (String::from(
+                    r#"
+package main
+
+// the entry point
+func main() {
+    s := `raw
+        /* not a comment */`
+    println(s, '"')
+}
+"#,
+                ) + "\0")
+                    .into(),
+            );
+            assert_eq!(get_meaningful_line_count(src.as_ptr(), GO_LANG, 0), 4);
+        }
+    }
+}
+
+#[cfg(test)]
+mod kotlin {
+    use super::*;
+    use std::ffi::CString;
+
+    #[test]
+    fn test_get_meaningful_line_count() {
+        unsafe {
+            let src = CString::from_vec_unchecked(
+                (String::from(
+                    r#"
+/* /* nested */ comment */
+fun main() {
+    val s = """
+        ${1 + 1}
+    """
+}
+"#,
+                ) + "\0")
+                    .into(),
+            );
+            assert_eq!(get_meaningful_line_count(src.as_ptr(), KOTLIN_LANG, 0), 3);
+        }
+    }
+}
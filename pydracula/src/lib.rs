@@ -7,6 +7,24 @@ enum Lang {
     Rust,
     C,
     Java,
+    Go,
+    Kotlin,
+    Cpp,
+    Swift,
+    Php,
+    Shell,
+    Lua,
+    Sql,
+    Postgres,
+    MySql,
+    TSql,
+    Haskell,
+    Dart,
+    Elixir,
+    Erlang,
+    Perl,
+    Julia,
+    R,
 }
 
 #[pyclass]
@@ -24,59 +42,261 @@ enum Language {
     Scala,
     CSharp,
     Ruby,
+    Go,
+    Kotlin,
+    Swift,
+    Dart,
+}
+
+/// The definition of a meaningful line, see `dracula::count::CountPolicy`
+#[pyclass]
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Meaningful,
+    PhysicalSloc,
+    Executable,
+    Review,
+}
+
+impl Kind {
+    fn count_policy(&self) -> dracula::count::CountPolicy {
+        use dracula::count::CountPolicy;
+        match self {
+            Kind::Meaningful => CountPolicy::MEANINGFUL,
+            Kind::PhysicalSloc => CountPolicy::PHYSICAL_SLOC,
+            Kind::Executable => CountPolicy::EXECUTABLE,
+            Kind::Review => CountPolicy::REVIEW,
+        }
+    }
 }
 
 impl Lang {
-    fn get_meaningful_line_indices(&self, src: &str) -> Vec<usize> {
+    fn get_meaningful_line_indices(&self, src: &str, kind: Kind) -> Vec<usize> {
         use dracula::count::*;
         use dracula::langs::*;
+        let policy = kind.count_policy();
         match self {
             Lang::Python => get_meaningful_line_indices::<Python>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Rust => get_meaningful_line_indices::<Rust>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::C => get_meaningful_line_indices::<C>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Java => get_meaningful_line_indices::<Java>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Go => get_meaningful_line_indices::<Go>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Kotlin => get_meaningful_line_indices::<Kotlin>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Cpp => get_meaningful_line_indices::<Cpp>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Swift => get_meaningful_line_indices::<Swift>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Php => get_meaningful_line_indices::<Php>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Shell => get_meaningful_line_indices::<Shell>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Lua => get_meaningful_line_indices::<Lua>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Sql => get_meaningful_line_indices::<Sql>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Postgres => get_meaningful_line_indices::<Postgres>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::MySql => get_meaningful_line_indices::<MySql>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::TSql => get_meaningful_line_indices::<TSql>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Haskell => get_meaningful_line_indices::<Haskell>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Dart => get_meaningful_line_indices::<Dart>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Elixir => get_meaningful_line_indices::<Elixir>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Erlang => get_meaningful_line_indices::<Erlang>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Perl => get_meaningful_line_indices::<Perl>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::Julia => get_meaningful_line_indices::<Julia>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect(),
+            Lang::R => get_meaningful_line_indices::<R>(src)
+                .with_policy(policy)
                 .flatten()
                 .collect(),
-            Lang::Rust => get_meaningful_line_indices::<Rust>(src).flatten().collect(),
-            Lang::C => get_meaningful_line_indices::<C>(src).flatten().collect(),
-            Lang::Java => get_meaningful_line_indices::<Java>(src).flatten().collect(),
         }
     }
     #[rustfmt::skip]
-    fn get_cleaned_source_code(&self, src: &str) -> String {
+    fn get_cleaned_source_code(&self, src: &str, kind: Kind) -> String {
         use dracula::count::*;
         use dracula::langs::*;
+        let policy = kind.count_policy();
         match self {
             Lang::Python => {
-                get_cleaned_source_code::<Python>(src)
+                get_cleaned_source_code_with_policy::<Python>(src, policy)
                     .unwrap_or_else(|| src.to_string())
             }
             Lang::Rust => {
-                get_cleaned_source_code::<Rust>(src)
+                get_cleaned_source_code_with_policy::<Rust>(src, policy)
                     .unwrap_or_else(|| src.to_string())
             },
             Lang::C => {
-                get_cleaned_source_code::<C>(src)
+                get_cleaned_source_code_with_policy::<C>(src, policy)
                     .unwrap_or_else(|| src.to_string())
             },
             Lang::Java => {
-                get_cleaned_source_code::<Java>(src)
+                get_cleaned_source_code_with_policy::<Java>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::Go => {
+                get_cleaned_source_code_with_policy::<Go>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::Kotlin => {
+                get_cleaned_source_code_with_policy::<Kotlin>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::Cpp => {
+                get_cleaned_source_code_with_policy::<Cpp>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::Swift => {
+                get_cleaned_source_code_with_policy::<Swift>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::Php => {
+                get_cleaned_source_code_with_policy::<Php>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::Shell => {
+                get_cleaned_source_code_with_policy::<Shell>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::Lua => {
+                get_cleaned_source_code_with_policy::<Lua>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::Sql => {
+                get_cleaned_source_code_with_policy::<Sql>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::Postgres => {
+                get_cleaned_source_code_with_policy::<Postgres>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::MySql => {
+                get_cleaned_source_code_with_policy::<MySql>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::TSql => {
+                get_cleaned_source_code_with_policy::<TSql>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::Haskell => {
+                get_cleaned_source_code_with_policy::<Haskell>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::Dart => {
+                get_cleaned_source_code_with_policy::<Dart>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::Elixir => {
+                get_cleaned_source_code_with_policy::<Elixir>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::Erlang => {
+                get_cleaned_source_code_with_policy::<Erlang>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::Perl => {
+                get_cleaned_source_code_with_policy::<Perl>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::Julia => {
+                get_cleaned_source_code_with_policy::<Julia>(src, policy)
+                    .unwrap_or_else(|| src.to_string())
+            },
+            Lang::R => {
+                get_cleaned_source_code_with_policy::<R>(src, policy)
                     .unwrap_or_else(|| src.to_string())
             },
         }
     }
-    fn get_count_of_meaningful_lines(&self, src: &str) -> usize {
+    fn get_count_of_meaningful_lines(&self, src: &str, kind: Kind) -> usize {
         use dracula::count::*;
         use dracula::langs::*;
+        let policy = kind.count_policy();
         match self {
-            Lang::Python => get_count_of_meaningful_lines::<Python>(src),
-            Lang::Rust => get_count_of_meaningful_lines::<Rust>(src),
-            Lang::C => get_count_of_meaningful_lines::<C>(src),
-            Lang::Java => get_count_of_meaningful_lines::<Java>(src),
+            Lang::Python => get_count_of_meaningful_lines_with_policy::<Python>(src, policy),
+            Lang::Rust => get_count_of_meaningful_lines_with_policy::<Rust>(src, policy),
+            Lang::C => get_count_of_meaningful_lines_with_policy::<C>(src, policy),
+            Lang::Java => get_count_of_meaningful_lines_with_policy::<Java>(src, policy),
+            Lang::Go => get_count_of_meaningful_lines_with_policy::<Go>(src, policy),
+            Lang::Kotlin => get_count_of_meaningful_lines_with_policy::<Kotlin>(src, policy),
+            Lang::Cpp => get_count_of_meaningful_lines_with_policy::<Cpp>(src, policy),
+            Lang::Swift => get_count_of_meaningful_lines_with_policy::<Swift>(src, policy),
+            Lang::Php => get_count_of_meaningful_lines_with_policy::<Php>(src, policy),
+            Lang::Shell => get_count_of_meaningful_lines_with_policy::<Shell>(src, policy),
+            Lang::Lua => get_count_of_meaningful_lines_with_policy::<Lua>(src, policy),
+            Lang::Sql => get_count_of_meaningful_lines_with_policy::<Sql>(src, policy),
+            Lang::Postgres => get_count_of_meaningful_lines_with_policy::<Postgres>(src, policy),
+            Lang::MySql => get_count_of_meaningful_lines_with_policy::<MySql>(src, policy),
+            Lang::TSql => get_count_of_meaningful_lines_with_policy::<TSql>(src, policy),
+            Lang::Haskell => get_count_of_meaningful_lines_with_policy::<Haskell>(src, policy),
+            Lang::Dart => get_count_of_meaningful_lines_with_policy::<Dart>(src, policy),
+            Lang::Elixir => get_count_of_meaningful_lines_with_policy::<Elixir>(src, policy),
+            Lang::Erlang => get_count_of_meaningful_lines_with_policy::<Erlang>(src, policy),
+            Lang::Perl => get_count_of_meaningful_lines_with_policy::<Perl>(src, policy),
+            Lang::Julia => get_count_of_meaningful_lines_with_policy::<Julia>(src, policy),
+            Lang::R => get_count_of_meaningful_lines_with_policy::<R>(src, policy),
         }
     }
 }
 
 #[pyfunction]
-fn get_meaningful_line_indices(lang: Lang, src: &str) -> Vec<usize> {
-    lang.get_meaningful_line_indices(src)
+#[pyo3(signature = (lang, src, kind = Kind::Meaningful))]
+fn get_meaningful_line_indices(lang: Lang, src: &str, kind: Kind) -> Vec<usize> {
+    lang.get_meaningful_line_indices(src, kind)
 }
 
 #[pyfunction]
@@ -97,25 +317,29 @@ fn get_lines_with_executable_code(lang: Language, src: &str) -> Option<Vec<usize
         Language::Typescript => TreeSitterLanguage::Typescript,
         Language::Tsx => TreeSitterLanguage::TSX,
         Language::Javascript | Language::Jsx => TreeSitterLanguage::Javascript,
-        // currently untested consider waiting for proper tests to be merged before using
         Language::Scala => TreeSitterLanguage::Scala,
         Language::CSharp => TreeSitterLanguage::CSharp,
         Language::Ruby => TreeSitterLanguage::Ruby,
         Language::Go => TreeSitterLanguage::Go,
+        Language::Kotlin => TreeSitterLanguage::Kotlin,
+        Language::Swift => TreeSitterLanguage::Swift,
+        Language::Dart => TreeSitterLanguage::Dart,
     };
     Parser::new(treesitter_lang)
-        .and_then(|parser| parser.non_executable_src_spans(src))
-        .map(|spans| get_lines_without_ranges(src, &spans))
+        .and_then(|mut parser| parser.non_executable_src_spans(src))
+        .map(|spans| get_lines_without_ranges(src, spans))
 }
 
 #[pyfunction]
-fn get_cleaned_source_code(lang: Lang, src: &str) -> String {
-    lang.get_cleaned_source_code(src)
+#[pyo3(signature = (lang, src, kind = Kind::Meaningful))]
+fn get_cleaned_source_code(lang: Lang, src: &str, kind: Kind) -> String {
+    lang.get_cleaned_source_code(src, kind)
 }
 
 #[pyfunction]
-fn get_count_of_meaningful_lines(lang: Lang, src: &str) -> usize {
-    lang.get_count_of_meaningful_lines(src)
+#[pyo3(signature = (lang, src, kind = Kind::Meaningful))]
+fn get_count_of_meaningful_lines(lang: Lang, src: &str, kind: Kind) -> usize {
+    lang.get_count_of_meaningful_lines(src, kind)
 }
 
 /// the python module definition
@@ -126,5 +350,6 @@ fn pydracula(_py: Python<'_>, m: &types::PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(get_count_of_meaningful_lines, m)?)?;
     m.add_function(wrap_pyfunction!(get_lines_with_executable_code, m)?)?;
     m.add_class::<Lang>()?;
+    m.add_class::<Kind>()?;
     Ok(())
 }
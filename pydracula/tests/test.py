@@ -1,4 +1,4 @@
-from pydracula import Lang, get_meaningful_line_indices
+from pydracula import Kind, Lang, get_count_of_meaningful_lines, get_meaningful_line_indices
 
 indices = get_meaningful_line_indices(
     Lang.C,
@@ -8,4 +8,30 @@ indices = get_meaningful_line_indices(
             }
             """,
 )
-assert len(indices) == 3
+assert len(indices) == 2
+
+src = """
+            int xyz() {
+                char* s = "multi \\
+                    line";
+            }
+            """
+assert get_count_of_meaningful_lines(Lang.C, src) == 2
+assert get_count_of_meaningful_lines(Lang.C, src, Kind.Review) == 3
+assert get_count_of_meaningful_lines(Lang.C, src, kind=Kind.PhysicalSloc) == 4
+
+src = """
+            func main() {
+                // a comment
+                r := '"'
+            }
+            """
+assert get_count_of_meaningful_lines(Lang.Go, src) == 2
+
+src = """
+            fun main() {
+                /* /* nested */ comment */
+                println("${1 + 1}")
+            }
+            """
+assert get_count_of_meaningful_lines(Lang.Kotlin, src) == 2
@@ -1,6 +1,66 @@
 //! unstable stablized rust api for count dracula
 
-use crate::parse::{Language, ParseOutput, Parser};
+use std::ops::Range;
+
+use crate::parse::{is_trivial_src, Composite, Language, ParseOutput, Parser};
+
+/// Decides which lines are counted as meaningful, with presets for the common
+/// definitions of a line of code.
+///
+/// Blank lines, comments and code that's never compiled are never counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CountPolicy {
+    /// count lines made up of only [`Language::TRIVIAL_TOKENS`], eg. `});`
+    pub trivial_lines: bool,
+    /// count lines with nothing but (a part of) a string on them
+    pub string_lines: bool,
+    /// count preprocessor directives, eg. `#include`
+    pub preprocessor_lines: bool,
+}
+
+impl CountPolicy {
+    /// Lines which have meaningful source on them, the default
+    pub const MEANINGFUL: Self = Self {
+        trivial_lines: false,
+        string_lines: false,
+        preprocessor_lines: true,
+    };
+    /// Physical source lines, any line that has some code on it
+    pub const PHYSICAL_SLOC: Self = Self {
+        trivial_lines: true,
+        string_lines: true,
+        preprocessor_lines: true,
+    };
+    /// Lines that can be executed, like test coverage tools count them
+    pub const EXECUTABLE: Self = Self {
+        trivial_lines: false,
+        string_lines: false,
+        preprocessor_lines: false,
+    };
+    /// Lines that need to be read during a review, which
+    /// includes the continuation lines of long strings
+    pub const REVIEW: Self = Self {
+        trivial_lines: false,
+        string_lines: true,
+        preprocessor_lines: true,
+    };
+
+    /// Checks if the parsed output makes its line meaningful
+    pub fn is_meaningful<L: Language>(&self, parse_output: &ParseOutput) -> bool {
+        match parse_output {
+            ParseOutput::Source(src) if self.trivial_lines => !is_trivial_src(src, &[]),
+            ParseOutput::String(_) => self.string_lines,
+            ParseOutput::Preprocessor(_) => self.preprocessor_lines,
+            _ => L::is_meaningful(parse_output),
+        }
+    }
+}
+
+impl Default for CountPolicy {
+    fn default() -> Self {
+        Self::MEANINGFUL
+    }
+}
 
 #[derive(Default)]
 struct Span {
@@ -17,9 +77,34 @@ pub struct ParseLineMeaningfulIndexIter<'a, L: Language> {
     line_index: usize,
     max_lines: usize,
     last_parsed_output: Option<ParseOutput<'a>>,
+    inactive_spans: Vec<Range<usize>>,
+    policy: CountPolicy,
     failed: bool,
 }
 
+impl<'a, L: Language> ParseLineMeaningfulIndexIter<'a, L> {
+    /// Replaces the spans of source which are never compiled, lines that lie
+    /// completely inside them are never meaningful.
+    ///
+    /// Defaults to [`Language::inactive_spans`].
+    pub fn with_inactive_spans(mut self, inactive_spans: Vec<Range<usize>>) -> Self {
+        self.inactive_spans = inactive_spans;
+        self
+    }
+
+    /// Sets the [`CountPolicy`] used to decide which lines are meaningful
+    pub fn with_policy(mut self, policy: CountPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+fn is_inactive_line(inactive_spans: &[Range<usize>], start: usize, end: usize) -> bool {
+    inactive_spans
+        .iter()
+        .any(|span| span.start <= start && end <= span.end)
+}
+
 impl<'a, L: Language> Iterator for ParseLineMeaningfulIndexIter<'a, L> {
     type Item = Option<usize>;
 
@@ -60,7 +145,13 @@ impl<'a, L: Language> Iterator for ParseLineMeaningfulIndexIter<'a, L> {
                         po_stack.push(parsed_output);
                     }
                 }
-                let is_meaningful_line = po_stack.iter().any(L::is_meaningful);
+                let is_meaningful_line = !line.trim().is_empty()
+                    && po_stack.iter().any(|po| self.policy.is_meaningful::<L>(po))
+                    && !is_inactive_line(
+                        &self.inactive_spans,
+                        self.line_span.start,
+                        self.line_span.end,
+                    );
                 if cfg!(dbg) {
                     eprintln!("{} == {}", idx, po_stack.iter().any(L::is_meaningful));
                     eprintln!("{:?}", po_stack);
@@ -93,6 +184,8 @@ pub fn get_meaningful_line_indices<L: Language + 'static>(
         parse_span: Span::default(),
         line_index: 0,
         last_parsed_output: None,
+        inactive_spans: L::inactive_spans(src),
+        policy: CountPolicy::default(),
         failed: false,
         max_lines: src.lines().count(),
     }
@@ -100,25 +193,62 @@ pub fn get_meaningful_line_indices<L: Language + 'static>(
 
 /// Uses the [`Parser`] to try and figure out the meaningful parts of the source
 pub fn get_cleaned_source_code<L: Language>(src: &str) -> Option<String> {
+    get_cleaned_source_code_with_policy::<L>(src, CountPolicy::default())
+}
+
+/// Same as [`get_cleaned_source_code`], but keeps the parts of source that are
+/// meaningful as per the given [`CountPolicy`]
+pub fn get_cleaned_source_code_with_policy<L: Language>(
+    src: &str,
+    policy: CountPolicy,
+) -> Option<String> {
+    get_cleaned_source_code_excluding::<L>(src, &L::inactive_spans(src), policy)
+}
+
+/// Same as [`get_cleaned_source_code_with_policy`], but drops the lines lying completely
+/// inside of the given `inactive_spans` instead of [`Language::inactive_spans`]
+pub fn get_cleaned_source_code_excluding<L: Language>(
+    src: &str,
+    inactive_spans: &[Range<usize>],
+    policy: CountPolicy,
+) -> Option<String> {
     let parsed = L::get_parser(src);
     let mut meaningful_src = String::default();
     let mut stack = vec![];
+    let mut line_start = 0;
+    let mut offset = 0;
     for p in parsed {
         if matches!(p, ParseOutput::Invalid(..)) {
             return None;
         }
+        offset += p.len();
         if matches!(p, ParseOutput::EOL(_) | ParseOutput::EOF) {
             let meaningful_src_len = meaningful_src.len();
-            for po in stack.iter() {
-                if let ParseOutput::Source(s) = po {
-                    if L::is_meaningful_src(s) {
-                        meaningful_src.push_str(s);
+            // trivial tokens are only dropped if the whole line is trivial
+            if stack.iter().any(|po| policy.is_meaningful::<L>(po))
+                && !is_inactive_line(inactive_spans, line_start, offset)
+            {
+                for po in stack.iter() {
+                    match po {
+                        // whitespace is only kept along with the strings around it
+                        ParseOutput::Source(s)
+                            if policy.string_lines || !is_trivial_src(s, &[]) =>
+                        {
+                            meaningful_src.push_str(s)
+                        }
+                        ParseOutput::String(s) | ParseOutput::Preprocessor(s)
+                            if policy.is_meaningful::<L>(po) =>
+                        {
+                            meaningful_src.push_str(s)
+                        }
+                        _ => {}
                     }
                 }
             }
             if matches!(p, ParseOutput::EOL(_)) && meaningful_src_len != meaningful_src.len() {
                 meaningful_src.push('\n');
             }
+            line_start = offset;
             stack.clear();
         } else {
             stack.push(p);
@@ -130,7 +260,80 @@ pub fn get_cleaned_source_code<L: Language>(src: &str) -> Option<String> {
 /// Uses the [`get_meaningful_line_indices`] function to build an iterator
 /// and count all meaningful lines
 pub fn get_count_of_meaningful_lines<L: Language + 'static>(src: &str) -> usize {
-    get_meaningful_line_indices::<L>(src).flatten().count()
+    get_count_of_meaningful_lines_with_policy::<L>(src, CountPolicy::default())
+}
+
+/// Same as [`get_count_of_meaningful_lines`], but counts the lines
+/// that are meaningful as per the given [`CountPolicy`]
+pub fn get_count_of_meaningful_lines_with_policy<L: Language + 'static>(
+    src: &str,
+    policy: CountPolicy,
+) -> usize {
+    get_meaningful_line_indices::<L>(src)
+        .with_policy(policy)
+        .flatten()
+        .count()
+}
+
+/// Blanks out the source outside (or inside, when `inside` is false) of the given spans,
+/// keeping newlines so that the line indices stay the same
+pub(crate) fn blank_source(src: &str, spans: &[Range<usize>], inside: bool) -> String {
+    let mut blanked = String::with_capacity(src.len());
+    for (i, ch) in src.char_indices() {
+        if ch == '\n' || spans.iter().any(|span| span.contains(&i)) == inside {
+            blanked.push(ch);
+        } else {
+            // keep the byte offsets of the rest of the source intact
+            blanked.extend(std::iter::repeat_n(' ', ch.len_utf8()));
+        }
+    }
+    blanked
+}
+
+/// Figures out meaningful line indices for each of the languages in a [`Composite`] source,
+/// grouped by the [`Embedded::name`](crate::parse::Embedded) of the language, host language first.
+///
+/// A line shared by languages, eg. `<script>let x = 1;</script>`, is counted for each of them.
+pub fn get_meaningful_line_indices_by_language<C: Composite>(
+    src: &str,
+    policy: CountPolicy,
+) -> Vec<(&'static str, Vec<usize>)> {
+    let regions = C::embedded_regions(src);
+    let mut by_language: Vec<(&'static str, Vec<usize>)> = vec![];
+    let spans = regions
+        .iter()
+        .map(|(_, span)| span.clone())
+        .collect::<Vec<_>>();
+    let host_src = blank_source(src, &spans, false);
+    by_language.push((C::HOST.name, C::HOST.meaningful_lines(&host_src, policy)));
+    for (embedded, _) in regions.iter() {
+        if by_language.iter().any(|(name, _)| *name == embedded.name) {
+            continue;
+        }
+        let spans = regions
+            .iter()
+            .filter(|(other, _)| other.name == embedded.name)
+            .map(|(_, span)| span.clone())
+            .collect::<Vec<_>>();
+        let embedded_src = blank_source(src, &spans, true);
+        by_language.push((
+            embedded.name,
+            embedded.meaningful_lines(&embedded_src, policy),
+        ));
+    }
+    by_language
+}
+
+/// Counts the meaningful lines for each of the languages in a [`Composite`] source,
+/// see [`get_meaningful_line_indices_by_language`]
+pub fn get_count_of_meaningful_lines_by_language<C: Composite>(
+    src: &str,
+    policy: CountPolicy,
+) -> Vec<(&'static str, usize)> {
+    get_meaningful_line_indices_by_language::<C>(src, policy)
+        .into_iter()
+        .map(|(name, lines)| (name, lines.len()))
+        .collect()
 }
 
 /// No halting tests for [`get_count_of_meaningful_lines`] as it uses
@@ -0,0 +1,138 @@
+// Excerpts from real code, trimmed to the parts which exercise the lexer. The
+// comment above each one names where it's from and its license, the copyright
+// stays with the original authors. Indentation may differ from upstream.
+
+// JEP 378: Text Blocks, https://openjdk.org/jeps/378
+// Copyright (c) Oracle and/or its affiliates, quoted from the examples of the JEP
+class Jep378TextBlocks {
+    String code =
+        """
+        String text = \"""
+            A text block inside a text block
+        \""";
+        """;
+
+    String tutorial1 =
+        """
+        A common character
+        in Java programs
+        is \"""";
+
+    String tutorial2 =
+        """
+        The empty string literal
+        is formed from " characters
+        as follows: \"\"""";
+
+    void print() {
+        System.out.println("""
+             1 "
+             2 ""
+             3 ""\"
+             4 ""\""
+             5 ""\"""
+             6 ""\"""\"
+             7 ""\"""\""
+             8 ""\"""\"""
+             9 ""\"""\"""\"
+            10 ""\"""\"""\""
+            11 ""\"""\"""\"""
+            12 ""\"""\"""\"""\"
+        """);
+    }
+
+    String text = """
+        Lorem ipsum dolor sit amet, consectetur adipiscing \
+        elit, sed do eiusmod tempor incididunt ut labore \
+        et dolore magna aliqua.\
+        """;
+}
+
+// ASM, org/objectweb/asm/util/Printer.java, https://asm.ow2.io
+// Copyright (c) 2000-2011 INRIA, France Telecom, BSD-3-Clause
+class AsmPrinter {
+  /**
+   * Appends a quoted string to the given string builder.
+   *
+   * @param stringBuilder the buffer where the string must be added.
+   * @param string the string to be added.
+   */
+  public static void appendString(final StringBuilder stringBuilder, final String string) {
+    stringBuilder.append('\"');
+    for (int i = 0; i < string.length(); ++i) {
+      char c = string.charAt(i);
+      if (c == '\n') {
+        stringBuilder.append("\\n");
+      } else if (c == '\r') {
+        stringBuilder.append("\\r");
+      } else if (c == '\\') {
+        stringBuilder.append("\\\\");
+      } else if (c == '"') {
+        stringBuilder.append("\\\"");
+      } else if (c < 0x20 || c > 0x7f) {
+        stringBuilder.append("\\u");
+        if (c < 0x10) {
+          stringBuilder.append("000");
+        } else if (c < 0x100) {
+          stringBuilder.append("00");
+        } else if (c < 0x1000) {
+          stringBuilder.append('0');
+        }
+        stringBuilder.append(Integer.toString(c, 16));
+      } else {
+        stringBuilder.append(c);
+      }
+    }
+    stringBuilder.append('\"');
+  }
+}
+
+// Guava, com/google/common/html/HtmlEscapers.java, https://github.com/google/guava
+// Copyright (C) 2009 The Guava Authors, Apache-2.0
+class GuavaHtmlEscapers {
+  private static final Escaper HTML_ESCAPER =
+      Escapers.builder()
+          .addEscape('"', "&quot;")
+          // Note: "&apos;" is not defined in HTML 4.01.
+          .addEscape('\'', "&#39;")
+          .addEscape('&', "&amp;")
+          .addEscape('<', "&lt;")
+          .addEscape('>', "&gt;")
+          .build();
+}
+
+// Apache Commons Lang, org/apache/commons/lang3/StringEscapeUtilsTest.java,
+// https://commons.apache.org/proper/commons-lang, Apache-2.0
+class CommonsLangStringEscapeUtilsTest {
+    @Test
+    public void testEscapeJava() throws IOException {
+        assertNull(StringEscapeUtils.escapeJava(null));
+
+        assertEscapeJava("empty string", "", "");
+        assertEscapeJava(FOO, FOO);
+        assertEscapeJava("tab", "\\t", "\t");
+        assertEscapeJava("backslash", "\\\\", "\\");
+        assertEscapeJava("single quote should not be escaped", "'", "'");
+        assertEscapeJava("\\\\\\b\\t\\r", "\\\b\t\r");
+        assertEscapeJava("\\u1234", "\u1234");
+        assertEscapeJava("\\u0234", "\u0234");
+        assertEscapeJava("\\u00EF", "\u00ef");
+        assertEscapeJava("\\u0001", "\u0001");
+        assertEscapeJava("Should use capitalized Unicode hex", "\\uABCD", "\uabcd");
+
+        assertEscapeJava("He didn't say, \\\"stop!\\\"",
+                "He didn't say, \"stop!\"");
+        assertEscapeJava("non-breaking space", "This space is non-breaking:" + "\\u00A0",
+                "This space is non-breaking:\u00a0");
+        assertEscapeJava("\\uABCD\\u1234\\u012C",
+                "\uABCD\u1234\u012C");
+    }
+}
+
+// Not from an upstream project: none of the excerpts above use a unicode escaped
+// quote or line break, which javac translates before lexing, so these are ours
+class UnicodeEscapes {
+    private static final char QUOTE = '\u0022';
+    private static final String QUOTED = \u0022a \"string\" in quotes\u0022; // comment
+    // a unicode escaped line break ends this comment \u000a static final int HIDDEN = 1;
+}
@@ -0,0 +1,63 @@
+class Jep378TextBlocks {
+    String code =
+    String tutorial1 =
+    String tutorial2 =
+    void print() {
+        System.out.println();
+    String text = ;
+class AsmPrinter {
+  public static void appendString(final StringBuilder stringBuilder, final String string) {
+    stringBuilder.append();
+    for (int i = 0; i < string.length(); ++i) {
+      char c = string.charAt(i);
+      if (c == ) {
+        stringBuilder.append();
+      } else if (c == ) {
+        stringBuilder.append();
+      } else if (c == ) {
+        stringBuilder.append();
+      } else if (c == ) {
+        stringBuilder.append();
+      } else if (c < 0x20 || c > 0x7f) {
+        stringBuilder.append();
+        if (c < 0x10) {
+          stringBuilder.append();
+        } else if (c < 0x100) {
+          stringBuilder.append();
+        } else if (c < 0x1000) {
+          stringBuilder.append();
+        stringBuilder.append(Integer.toString(c, 16));
+      } else {
+        stringBuilder.append(c);
+    stringBuilder.append();
+class GuavaHtmlEscapers {
+  private static final Escaper HTML_ESCAPER =
+      Escapers.builder()
+          .addEscape(, )
+          .addEscape(, )
+          .addEscape(, )
+          .addEscape(, )
+          .addEscape(, )
+          .build();
+class CommonsLangStringEscapeUtilsTest {
+    @Test
+    public void testEscapeJava() throws IOException {
+        assertNull(StringEscapeUtils.escapeJava(null));
+        assertEscapeJava(, , );
+        assertEscapeJava(FOO, FOO);
+        assertEscapeJava(, , );
+        assertEscapeJava(, , );
+        assertEscapeJava(, , );
+        assertEscapeJava(, );
+        assertEscapeJava(, );
+        assertEscapeJava(, );
+        assertEscapeJava(, );
+        assertEscapeJava(, );
+        assertEscapeJava(, , );
+        assertEscapeJava(,
+        assertEscapeJava(,  + ,
+        assertEscapeJava(,
+class UnicodeEscapes {
+    private static final char QUOTE = ;
+    private static final String QUOTED = ; 
+ static final int HIDDEN = 1;
@@ -11,6 +11,5 @@ BAD_ESCAPE_WITH_PREFIX =
 BAD_ESCAPE_WITH_BACKSLASH = 
 BAD_ESCAPE_BLOCK = 
 BAD_ESCAPE_PARENS = (
-)  
 GOOD_ESCAPE = 
 BAD_ESCAPE_BUT_RAW = 
@@ -1,9 +1,13 @@
-use crate::parse::{ItemRange, Language, ParseItem, Matcher};
+use super::preprocessor::{Preprocessor, DIRECTIVE};
+use super::C_LIKE_TRIVIAL_TOKENS;
+use crate::parse::{ItemRange, Language, ParseItem};
 
 /// C supports escapes in single line comments as well
 pub struct C;
 impl Language for C {
     const PARSE_ITEMS: &'static [ParseItem] = &[
+        // `#include`, `#define`, `#if`, etc.
+        DIRECTIVE,
         // single line comment
         ParseItem::Escaped(&ParseItem::Comment(
             ItemRange::fixed_start("//").pre_fixed_end("\n"),
@@ -19,23 +23,10 @@ impl Language for C {
             ItemRange::fixed_start("\"").fixed_end("\""),
             false,
         )),
-        // raw string comment
-        ParseItem::UnEscaped(&ParseItem::String(
-            ItemRange::start_matcher(
-                Matcher::Exact("R\""),
-                Matcher::AnyAlphaNumeric,
-                Matcher::Exact("("),
-            )
-            .end_matcher(
-                Matcher::Exact(")"),
-                Matcher::AnyAlphaNumeric,
-                Matcher::Exact("\""),
-            ),
-            true,
-        )), // R"UNIQUE_KEY( RAW STRING )UNIQUE_KEY"
     ];
-    fn is_meaningful_src(src: &str) -> bool {
-        !src.chars()
-            .all(|ch| char::is_whitespace(ch) || ch == '}' || ch == '{')
+    const TRIVIAL_TOKENS: &'static [&'static str] = C_LIKE_TRIVIAL_TOKENS;
+    /// Blocks like `#if 0` which are never compiled
+    fn inactive_spans(src: &str) -> Vec<std::ops::Range<usize>> {
+        Preprocessor::new().inactive_spans::<Self>(src)
     }
-}
\ No newline at end of file
+}
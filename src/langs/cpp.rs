@@ -0,0 +1,93 @@
+use super::preprocessor::{Preprocessor, DIRECTIVE};
+use super::C_LIKE_TRIVIAL_TOKENS;
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
+
+/// Strips the encoding prefix of a string or char literal, eg. `u8` in `u8R"(..)"`
+fn strip_encoding_prefix(src: &str) -> &str {
+    ["u8", "u", "U", "L"]
+        .iter()
+        .find_map(|prefix| src.strip_prefix(prefix))
+        .unwrap_or(src)
+}
+
+/// `R"` of a raw string along with its encoding prefix, eg. `u8R"`
+const RAW_STRING_START: Matcher = Matcher::Fn(
+    &(|src| {
+        let rest = strip_encoding_prefix(src);
+        rest.starts_with("R\"")
+            .then(|| &src[..src.len() - rest.len() + 2])
+    }),
+);
+
+/// Raw string delimiters are up to 16 characters other than parens,
+/// backslashes and whitespace, eg. `--` in `R"--( )" )--"`
+const RAW_STRING_DELIMITER: Matcher = Matcher::Fn(
+    &(|src| {
+        let end = src
+            .find(|ch: char| matches!(ch, '(' | ')' | '\\' | '"') || ch.is_whitespace())
+            .unwrap_or(src.len());
+        (end <= 16).then(|| &src[..end])
+    }),
+);
+
+/// `'` of a char literal along with its encoding prefix, eg. `u8'`
+const CHAR_START: Matcher = Matcher::Fn(
+    &(|src| {
+        let rest = strip_encoding_prefix(src);
+        rest.starts_with('\'')
+            .then(|| &src[..src.len() - rest.len() + 1])
+    }),
+);
+
+/// Char literals can't follow a digit or an identifier, which is
+/// how digit separators like `1'000'000` are told apart from them
+const CHAR_PRECEDED_BY: Matcher = Matcher::Fn(
+    &(|before| (!before.ends_with(|ch: char| ch.is_alphanumeric() || ch == '_')).then_some("")),
+);
+
+/// C++ has raw strings and digit separators on top of C
+pub struct Cpp;
+impl Language for Cpp {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        // `#include`, `#define`, `#if`, etc.
+        DIRECTIVE,
+        // single line comment
+        ParseItem::Escaped(&ParseItem::Comment(
+            ItemRange::fixed_start("//").pre_fixed_end("\n"),
+            false,
+        )),
+        // multiline line comment
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("/*").fixed_end("*/"),
+            false,
+        )),
+        // raw string, R"UNIQUE_KEY( RAW STRING )UNIQUE_KEY"
+        ParseItem::UnEscaped(&ParseItem::String(
+            ItemRange::start_matcher(RAW_STRING_START, RAW_STRING_DELIMITER, Matcher::Exact("("))
+                .end_matcher(
+                    Matcher::Exact(")"),
+                    RAW_STRING_DELIMITER,
+                    Matcher::Exact("\""),
+                ),
+            true,
+        )),
+        // simple string
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\"").fixed_end("\""),
+            false,
+        )),
+        // char literal, eg. '"'
+        ParseItem::Preceded(
+            CHAR_PRECEDED_BY,
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(CHAR_START, Matcher::Empty, Matcher::Empty).fixed_end("'"),
+                false,
+            )),
+        ),
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = C_LIKE_TRIVIAL_TOKENS;
+    /// Blocks like `#if 0` which are never compiled
+    fn inactive_spans(src: &str) -> Vec<std::ops::Range<usize>> {
+        Preprocessor::new().inactive_spans::<Self>(src)
+    }
+}
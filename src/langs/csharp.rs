@@ -1,19 +1,100 @@
-use crate::parse::{ItemRange, Language, ParseItem};
+use super::C_LIKE_TRIVIAL_TOKENS;
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
 
+/// Raw string literals begin and end with three or more quotes
+const RAW_QUOTES: Matcher = Matcher::Fn(
+    &(|src| {
+        let quotes = src.len() - src.trim_start_matches('"').len();
+        (quotes >= 3).then(|| &src[..quotes])
+    }),
+);
+
+/// `""` in verbatim strings is a quote and doesn't end the string
+const DOUBLED_QUOTE: ParseItem = ParseItem::String(
+    ItemRange::fixed_start("\"\"").end_matcher(Matcher::Empty, Matcher::Empty, Matcher::Empty),
+    false,
+);
+
+/// `{{` in interpolated strings is a brace and doesn't begin an interpolation
+const DOUBLED_BRACE: ParseItem = ParseItem::String(
+    ItemRange::fixed_start("{{").end_matcher(Matcher::Empty, Matcher::Empty, Matcher::Empty),
+    false,
+);
+
+const INTERPOLATION: ParseItem =
+    ParseItem::SameAsSrcInterpolation(ItemRange::fixed_start("{").fixed_end("}"));
+
+/// C#, raw strings with more than one `$` need as many braces for interpolation,
+/// those are lexed as strings without any interpolation
 pub struct Csharp;
 impl Language for Csharp {
-    const PARSE_ITEMS: &'static [ParseItem] = &[
-        ParseItem::UnEscaped(&ParseItem::Comment(
-            ItemRange::fixed_start("//").pre_fixed_end("\n"),
-            false,
-        )),
-        ParseItem::UnEscaped(&ParseItem::Comment(
-            ItemRange::fixed_start("/*").fixed_end("*/"),
-            false,
-        )),
-        ParseItem::Escaped(&ParseItem::String(
-            ItemRange::fixed_start("\"").fixed_end("\""),
-            false,
-        )),
-    ];
-}
\ No newline at end of file
+    const PARSE_ITEMS: &'static [ParseItem] =
+        &[
+            ParseItem::UnEscaped(&ParseItem::Comment(
+                ItemRange::fixed_start("//").pre_fixed_end("\n"),
+                false,
+            )),
+            ParseItem::UnEscaped(&ParseItem::Comment(
+                ItemRange::fixed_start("/*").fixed_end("*/"),
+                false,
+            )),
+            // interpolated raw strings, eg. `$"""{x}"""`
+            ParseItem::WithInner(
+                &ParseItem::UnEscaped(&ParseItem::String(
+                    ItemRange::start_matcher(Matcher::Exact("$"), RAW_QUOTES, Matcher::Empty)
+                        .end_matcher(Matcher::Empty, Matcher::Repeat("\""), Matcher::Empty),
+                    true,
+                )),
+                &[INTERPOLATION],
+            ),
+            // raw strings, eg. `"""a "quoted" b"""` and `$$"""{{x}}"""`
+            ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::start_matcher(Matcher::Repeat("$"), RAW_QUOTES, Matcher::Empty)
+                    .end_matcher(Matcher::Empty, Matcher::Repeat("\""), Matcher::Empty),
+                true,
+            )),
+            // interpolated verbatim strings, eg. `$@"{dir}\file"`
+            ParseItem::WithInner(
+                &ParseItem::UnEscaped(&ParseItem::String(
+                    ItemRange::start_matcher(
+                        Matcher::Fn(
+                            &(|src| {
+                                (src.starts_with("$@\"") || src.starts_with("@$\""))
+                                    .then(|| &src[..3])
+                            }),
+                        ),
+                        Matcher::Empty,
+                        Matcher::Empty,
+                    )
+                    .fixed_end("\""),
+                    false,
+                )),
+                &[DOUBLED_QUOTE, DOUBLED_BRACE, INTERPOLATION],
+            ),
+            // verbatim strings, eg. `@"C:\dir"`
+            ParseItem::WithInner(
+                &ParseItem::UnEscaped(&ParseItem::String(
+                    ItemRange::fixed_start("@\"").fixed_end("\""),
+                    false,
+                )),
+                &[DOUBLED_QUOTE],
+            ),
+            // interpolated strings, eg. `$"{x}"`
+            ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::fixed_start("$\"").fixed_end("\""),
+                    false,
+                )),
+                &[DOUBLED_BRACE, INTERPOLATION],
+            ),
+            ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("\"").fixed_end("\""),
+                false,
+            )),
+            ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("\'").fixed_end("\'"),
+                false,
+            )),
+        ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = C_LIKE_TRIVIAL_TOKENS;
+}
@@ -0,0 +1,44 @@
+use crate::parse::{ItemRange, Language, ParseItem};
+
+pub struct Css;
+impl Language for Css {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("/*").fixed_end("*/"),
+            false,
+        )),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\"").fixed_end("\""),
+            false,
+        )),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\'").fixed_end("\'"),
+            false,
+        )),
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = &["{", "}", ";"];
+}
+
+/// Css with `//` line comments, also used for less and sass
+pub struct Scss;
+impl Language for Scss {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("//").pre_fixed_end("\n"),
+            false,
+        )),
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("/*").fixed_end("*/"),
+            false,
+        )),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\"").fixed_end("\""),
+            false,
+        )),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\'").fixed_end("\'"),
+            false,
+        )),
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = &["{", "}", ";"];
+}
@@ -0,0 +1,106 @@
+use super::C_LIKE_TRIVIAL_TOKENS;
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
+
+/// `$name` and `${expr}` interpolation in strings is source
+const DART_INTERPOLATION: &[ParseItem] = &[
+    ParseItem::SameAsSrcInterpolation(ItemRange::fixed_start("${").fixed_end("}")),
+    ParseItem::SameAsSrcInterpolation(
+        ItemRange::start_matcher(
+            Matcher::Exact("$"),
+            Matcher::Fn(
+                &(|src| {
+                    src.starts_with(|ch: char| ch.is_alphabetic() || ch == '_')
+                        .then(|| {
+                            let end = src
+                                .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                                .unwrap_or(src.len());
+                            &src[..end]
+                        })
+                }),
+            ),
+            Matcher::Empty,
+        )
+        .end_matcher(Matcher::Empty, Matcher::Empty, Matcher::Empty),
+    ),
+];
+
+/// The `r` of raw strings is only a prefix at the start of a word, eg. not in `bar'...'`
+const RAW_PRECEDED_BY: Matcher = Matcher::Fn(
+    &(|before| {
+        (!before.ends_with(|ch: char| ch.is_alphanumeric() || ch == '_' || ch == '$')).then_some("")
+    }),
+);
+
+/// Dart, block comments nest and raw strings (`r'...'`) have neither escapes
+/// nor interpolation
+pub struct Dart;
+impl Language for Dart {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        // single line and `///` doc comments
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("//").pre_fixed_end("\n"),
+            false,
+        )),
+        ParseItem::Nested(&ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("/*").fixed_end("*/"),
+            false,
+        ))),
+        ParseItem::Preceded(
+            RAW_PRECEDED_BY,
+            &ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::fixed_start("r'''").fixed_end("'''"),
+                false,
+            )),
+        ),
+        ParseItem::Preceded(
+            RAW_PRECEDED_BY,
+            &ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::fixed_start("r\"\"\"").fixed_end("\"\"\""),
+                false,
+            )),
+        ),
+        ParseItem::Preceded(
+            RAW_PRECEDED_BY,
+            &ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::fixed_start("r'").fixed_end("'"),
+                false,
+            )),
+        ),
+        ParseItem::Preceded(
+            RAW_PRECEDED_BY,
+            &ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::fixed_start("r\"").fixed_end("\""),
+                false,
+            )),
+        ),
+        ParseItem::WithInner(
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("'''").fixed_end("'''"),
+                false,
+            )),
+            DART_INTERPOLATION,
+        ),
+        ParseItem::WithInner(
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("\"\"\"").fixed_end("\"\"\""),
+                false,
+            )),
+            DART_INTERPOLATION,
+        ),
+        ParseItem::WithInner(
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("'").fixed_end("'"),
+                false,
+            )),
+            DART_INTERPOLATION,
+        ),
+        ParseItem::WithInner(
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("\"").fixed_end("\""),
+                false,
+            )),
+            DART_INTERPOLATION,
+        ),
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = C_LIKE_TRIVIAL_TOKENS;
+}
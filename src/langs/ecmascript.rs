@@ -0,0 +1,95 @@
+//! Items shared by JavaScript and TypeScript, which lex the same literals.
+
+use crate::parse::{ItemRange, Matcher, ParseItem};
+
+/// Keywords after which a `/` begins a regex literal instead of a division
+const REGEX_KEYWORDS: &[&str] = &[
+    "return",
+    "typeof",
+    "instanceof",
+    "case",
+    "do",
+    "else",
+    "in",
+    "of",
+    "new",
+    "delete",
+    "void",
+    "throw",
+    "yield",
+    "await",
+];
+
+/// A `/` begins a regex literal at the start of the source, after an operator or
+/// punctuation, or after a keyword, eg. `x = /a/` but not `a / b / c`
+const REGEX_PRECEDED_BY: Matcher = Matcher::Fn(
+    &(|before| {
+        let before = before.trim_end();
+        let is_operator = before.is_empty()
+            || before.ends_with([
+                '(', ',', '=', ':', '[', '!', '&', '|', '?', '{', '}', ';', '+', '-', '*', '%',
+                '<', '>', '~', '^',
+            ]);
+        let is_keyword = || {
+            REGEX_KEYWORDS.iter().any(|kw| {
+                before.ends_with(kw)
+                    && !before[..before.len() - kw.len()].ends_with(|ch: char| {
+                        ch.is_alphanumeric() || ch == '_' || ch == '$' || ch == '.'
+                    })
+            })
+        };
+        (is_operator || is_keyword()).then_some("")
+    }),
+);
+
+/// Matches a whole regex literal on a single line, including its flags, eg. `/[/"]+/g`
+const REGEX_LITERAL: Matcher = Matcher::Fn(
+    &(|src| {
+        if !src.starts_with('/') || src[1..].starts_with(['/', '*']) {
+            return None;
+        }
+        let (mut escape, mut class) = (false, false);
+        let end = src
+            .char_indices()
+            .skip(1)
+            .find(|&(_, ch)| {
+                match ch {
+                    _ if escape => escape = false,
+                    '\\' => escape = true,
+                    '[' => class = true,
+                    ']' => class = false,
+                    '/' if !class => return true,
+                    _ => {}
+                }
+                false
+            })
+            .map(|(i, _)| i + 1)
+            .filter(|&i| !src[..i].contains('\n'))?;
+        let flags = src[end..]
+            .find(|ch: char| !ch.is_ascii_alphabetic())
+            .unwrap_or(src.len() - end);
+        Some(&src[..end + flags])
+    }),
+);
+
+pub(crate) const TEMPLATE_LITERAL: ParseItem = ParseItem::WithInner(
+    &ParseItem::Escaped(&ParseItem::String(
+        ItemRange::fixed_start("`").fixed_end("`"),
+        false,
+    )),
+    &[ParseItem::SameAsSrcInterpolation(
+        ItemRange::fixed_start("${").fixed_end("}"),
+    )],
+);
+
+pub(crate) const REGEX: ParseItem = ParseItem::Preceded(
+    REGEX_PRECEDED_BY,
+    &ParseItem::UnEscaped(&ParseItem::String(
+        ItemRange::start_matcher(REGEX_LITERAL, Matcher::Empty, Matcher::Empty).end_matcher(
+            Matcher::Empty,
+            Matcher::Empty,
+            Matcher::Empty,
+        ),
+        false,
+    )),
+);
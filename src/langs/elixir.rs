@@ -0,0 +1,170 @@
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
+
+/// `#{}` in strings, charlists and sigils is source
+const ELIXIR_INTERPOLATION: &[ParseItem] = &[ParseItem::SameAsSrcInterpolation(
+    ItemRange::fixed_start("#{").fixed_end("}"),
+)];
+
+/// `@moduledoc`, `@doc` and `@typedoc` heredocs, eg. `@doc ~S"""`
+const DOC_HEREDOC_START: Matcher = Matcher::Fn(
+    &(|src| {
+        let rest = src.strip_prefix('@')?;
+        let rest = ["moduledoc", "typedoc", "doc"]
+            .iter()
+            .find_map(|attribute| rest.strip_prefix(attribute))?
+            .trim_start_matches([' ', '\t']);
+        let rest = rest
+            .strip_prefix("~S")
+            .or_else(|| rest.strip_prefix("~s"))
+            .unwrap_or(rest);
+        rest.starts_with("\"\"\"")
+            .then(|| &src[..src.len() - rest.len() + 3])
+    }),
+);
+
+/// `~` followed by a lowercase letter, or by uppercase letters and digits, eg. `~r` or `~HTML`
+const SIGIL_START: Matcher = Matcher::Fn(
+    &(|src| {
+        let rest = src.strip_prefix('~')?;
+        let len = match rest.chars().next()? {
+            'a'..='z' => 1,
+            'A'..='Z' => rest
+                .find(|ch: char| !(ch.is_ascii_uppercase() || ch.is_ascii_digit()))
+                .unwrap_or(rest.len()),
+            _ => return None,
+        };
+        Some(&src[..1 + len])
+    }),
+);
+
+/// Delimiters which aren't brackets end the sigil with the same char, eg. `~r/a/`
+const SIGIL_DELIMITER: Matcher =
+    Matcher::Fn(&(|src| src.starts_with(['/', '|', '"', '\'']).then(|| &src[..1])));
+
+/// `?a` is the code point of `a`, eg. `?"` and `?#` aren't a string or a comment
+const CHAR_LITERAL: Matcher = Matcher::Fn(
+    &(|src| {
+        let rest = src.strip_prefix('?')?;
+        let rest = rest.strip_prefix('\\').unwrap_or(rest);
+        let ch = rest.chars().next()?;
+        Some(&src[..src.len() - rest.len() + ch.len_utf8()])
+    }),
+);
+
+/// `?` ends function names like `valid?`
+const CHAR_LITERAL_PRECEDED_BY: Matcher = Matcher::Fn(
+    &(|before| (!before.ends_with(|ch: char| ch.is_alphanumeric() || ch == '_')).then_some("")),
+);
+
+/// Elixir, documentation heredocs are comments. Interpolation is also picked up
+/// in uppercase sigils, which don't have it.
+pub struct Elixir;
+impl Language for Elixir {
+    const PARSE_ITEMS: &'static [ParseItem] =
+        &[
+            ParseItem::UnEscaped(&ParseItem::Comment(
+                ItemRange::start_matcher(DOC_HEREDOC_START, Matcher::Empty, Matcher::Empty)
+                    .fixed_end("\"\"\""),
+                false,
+            )),
+            ParseItem::UnEscaped(&ParseItem::Comment(
+                ItemRange::fixed_start("#").pre_fixed_end("\n"),
+                false,
+            )),
+            ParseItem::Preceded(
+                CHAR_LITERAL_PRECEDED_BY,
+                &ParseItem::UnEscaped(&ParseItem::String(
+                    ItemRange::start_matcher(CHAR_LITERAL, Matcher::Empty, Matcher::Empty)
+                        .end_matcher(Matcher::Empty, Matcher::Empty, Matcher::Empty),
+                    false,
+                )),
+            ),
+            // sigils, eg. `~s"""`, `~r{a}i`, `~w(a b)` and `~S|#{not interpolated}|`
+            ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::start_matcher(SIGIL_START, Matcher::Empty, Matcher::Exact("\"\"\""))
+                        .fixed_end("\"\"\""),
+                    false,
+                )),
+                ELIXIR_INTERPOLATION,
+            ),
+            ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::start_matcher(SIGIL_START, Matcher::Empty, Matcher::Exact("'''"))
+                        .fixed_end("'''"),
+                    false,
+                )),
+                ELIXIR_INTERPOLATION,
+            ),
+            ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::start_matcher(SIGIL_START, Matcher::Empty, Matcher::Exact("("))
+                        .fixed_end(")"),
+                    false,
+                )),
+                ELIXIR_INTERPOLATION,
+            ),
+            ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::start_matcher(SIGIL_START, Matcher::Empty, Matcher::Exact("["))
+                        .fixed_end("]"),
+                    false,
+                )),
+                ELIXIR_INTERPOLATION,
+            ),
+            ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::start_matcher(SIGIL_START, Matcher::Empty, Matcher::Exact("{"))
+                        .fixed_end("}"),
+                    false,
+                )),
+                ELIXIR_INTERPOLATION,
+            ),
+            ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::start_matcher(SIGIL_START, Matcher::Empty, Matcher::Exact("<"))
+                        .fixed_end(">"),
+                    false,
+                )),
+                ELIXIR_INTERPOLATION,
+            ),
+            ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::start_matcher(SIGIL_START, SIGIL_DELIMITER, Matcher::Empty)
+                        .end_matcher(Matcher::Empty, SIGIL_DELIMITER, Matcher::Empty),
+                    true,
+                )),
+                ELIXIR_INTERPOLATION,
+            ),
+            ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::fixed_start("\"\"\"").fixed_end("\"\"\""),
+                    false,
+                )),
+                ELIXIR_INTERPOLATION,
+            ),
+            ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::fixed_start("'''").fixed_end("'''"),
+                    false,
+                )),
+                ELIXIR_INTERPOLATION,
+            ),
+            ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::fixed_start("\"").fixed_end("\""),
+                    false,
+                )),
+                ELIXIR_INTERPOLATION,
+            ),
+            // charlists
+            ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::fixed_start("\'").fixed_end("\'"),
+                    false,
+                )),
+                ELIXIR_INTERPOLATION,
+            ),
+        ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = &["end", "(", ")", "[", "]", "{", "}", ","];
+}
@@ -0,0 +1,40 @@
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
+
+/// `$a` is the code point of `a`, eg. `$"` and `$%` aren't a string or a comment
+const CHAR_LITERAL: Matcher = Matcher::Fn(
+    &(|src| {
+        let rest = src.strip_prefix('$')?;
+        let rest = rest.strip_prefix('\\').unwrap_or(rest);
+        let ch = rest.chars().next()?;
+        Some(&src[..src.len() - rest.len() + ch.len_utf8()])
+    }),
+);
+
+/// Erlang, quoted atoms like `'%'` are source
+pub struct Erlang;
+impl Language for Erlang {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("%").pre_fixed_end("\n"),
+            false,
+        )),
+        ParseItem::UnEscaped(&ParseItem::String(
+            ItemRange::start_matcher(CHAR_LITERAL, Matcher::Empty, Matcher::Empty).end_matcher(
+                Matcher::Empty,
+                Matcher::Empty,
+                Matcher::Empty,
+            ),
+            false,
+        )),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\"").fixed_end("\""),
+            false,
+        )),
+        ParseItem::Escaped(&ParseItem::InSource(
+            ItemRange::fixed_start("\'").fixed_end("\'"),
+            false,
+        )),
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] =
+        &["end", "(", ")", "[", "]", "{", "}", ",", ";", "."];
+}
@@ -0,0 +1,31 @@
+use super::C_LIKE_TRIVIAL_TOKENS;
+use crate::parse::{ItemRange, Language, ParseItem};
+
+/// Go raw strings are delimited by backticks and can't contain them,
+/// runes are parsed like strings as `'"'` would otherwise start one
+pub struct Go;
+impl Language for Go {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("//").pre_fixed_end("\n"),
+            false,
+        )),
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("/*").fixed_end("*/"),
+            false,
+        )),
+        ParseItem::UnEscaped(&ParseItem::String(
+            ItemRange::fixed_start("`").fixed_end("`"),
+            false,
+        )),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\"").fixed_end("\""),
+            false,
+        )),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\'").fixed_end("\'"),
+            false,
+        )),
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = C_LIKE_TRIVIAL_TOKENS;
+}
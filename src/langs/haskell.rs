@@ -0,0 +1,69 @@
+use super::CHAR_LITERAL;
+use super::C_LIKE_TRIVIAL_TOKENS;
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
+
+const SYMBOLS: &str = "!#$%&*+./<=>?@\\^|-~:";
+
+/// `--` and longer runs of dashes begin a comment, unless they are a part
+/// of an operator like `-->` or `|--`
+const LINE_COMMENT_START: Matcher = Matcher::Fn(
+    &(|src| {
+        let dashes = src.len() - src.trim_start_matches('-').len();
+        (dashes >= 2 && !src[dashes..].starts_with(|ch| SYMBOLS.contains(ch)))
+            .then(|| &src[..dashes])
+    }),
+);
+
+const NOT_AFTER_SYMBOL: Matcher =
+    Matcher::Fn(&(|before| (!before.ends_with(|ch| SYMBOLS.contains(ch))).then_some("")));
+
+/// Primes are a part of identifiers, eg. `x'` and `foldl'`
+const NOT_AFTER_IDENTIFIER: Matcher = Matcher::Fn(
+    &(|before| {
+        (!before.ends_with(|ch: char| ch.is_alphanumeric() || ch == '_' || ch == '\''))
+            .then_some("")
+    }),
+);
+
+/// Haskell, also works for Elm and PureScript
+pub struct Haskell;
+impl Language for Haskell {
+    const PARSE_ITEMS: &'static [ParseItem] =
+        &[
+            // `{-# LANGUAGE ... #-}` and other pragmas
+            ParseItem::UnEscaped(&ParseItem::Preprocessor(
+                ItemRange::fixed_start("{-#").fixed_end("#-}"),
+                false,
+            )),
+            ParseItem::Nested(&ParseItem::UnEscaped(&ParseItem::Comment(
+                ItemRange::fixed_start("{-").fixed_end("-}"),
+                false,
+            ))),
+            ParseItem::Preceded(
+                NOT_AFTER_SYMBOL,
+                &ParseItem::UnEscaped(&ParseItem::Comment(
+                    ItemRange::start_matcher(LINE_COMMENT_START, Matcher::Empty, Matcher::Empty)
+                        .pre_fixed_end("\n"),
+                    false,
+                )),
+            ),
+            // triple quoted strings of Elm and PureScript
+            ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::fixed_start("\"\"\"").fixed_end("\"\"\""),
+                false,
+            )),
+            ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("\"").fixed_end("\""),
+                false,
+            )),
+            ParseItem::Preceded(
+                NOT_AFTER_IDENTIFIER,
+                &ParseItem::UnEscaped(&ParseItem::String(
+                    ItemRange::start_matcher(CHAR_LITERAL, Matcher::Empty, Matcher::Empty)
+                        .end_matcher(Matcher::Empty, Matcher::Empty, Matcher::Empty),
+                    false,
+                )),
+            ),
+        ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = C_LIKE_TRIVIAL_TOKENS;
+}
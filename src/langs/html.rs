@@ -0,0 +1,58 @@
+use crate::parse::{get_attribute, Composite, Embedded, ItemRange, Language, ParseItem};
+
+use super::{Css, Scss, Tsx, TypeScript, JS};
+
+pub struct Html;
+impl Language for Html {
+    const PARSE_ITEMS: &'static [ParseItem] = &[ParseItem::UnEscaped(&ParseItem::Comment(
+        ItemRange::fixed_start("<!--").fixed_end("-->"),
+        false,
+    ))];
+}
+
+/// Picks the language of `<script>` and `<style>` blocks from their `lang` or `type` attributes
+pub(super) fn embedded_script_or_style(tag: &str, attributes: &str) -> Option<Embedded> {
+    let lang = get_attribute(attributes, "lang").map(str::to_ascii_lowercase);
+    match (tag, lang.as_deref()) {
+        ("style", None | Some("css" | "postcss")) => Some(Embedded::of::<Css>("css")),
+        // stylus has `//` comments like scss
+        ("style", Some("scss" | "sass" | "less" | "stylus")) => Some(Embedded::of::<Scss>("scss")),
+        ("script", Some("ts" | "typescript")) => Some(Embedded::of::<TypeScript>("typescript")),
+        ("script", Some("tsx")) => Some(Embedded::of::<Tsx>("tsx")),
+        ("script", Some("js" | "javascript" | "jsx")) => Some(Embedded::of::<JS>("javascript")),
+        ("script", None) => match get_attribute(attributes, "type") {
+            // json, templates and the likes are not scripts
+            None | Some("module" | "text/javascript" | "application/javascript") => {
+                Some(Embedded::of::<JS>("javascript"))
+            }
+            Some("text/typescript" | "application/typescript") => {
+                Some(Embedded::of::<TypeScript>("typescript"))
+            }
+            Some(_) => None,
+        },
+        _ => None,
+    }
+}
+
+/// Markup hosted in html, with `<script>` and `<style>` blocks picked by
+/// [`embedded_script_or_style`], eg. `<script lang="ts">` is TypeScript
+macro_rules! html_composite {
+    ($($name:ident),+) => {
+        $(
+            impl Composite for $name {
+                const HOST: Embedded = Embedded::of::<Html>("html");
+                fn embedded(tag: &str, attributes: &str) -> Option<Embedded> {
+                    embedded_script_or_style(tag, attributes)
+                }
+            }
+        )+
+    };
+}
+
+html_composite!(Html, Vue, Svelte);
+
+/// Vue single file components, `<template>` is counted as html
+pub struct Vue;
+
+/// Svelte components, the markup outside of `<script>` and `<style>` is counted as html
+pub struct Svelte;
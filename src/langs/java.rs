@@ -1,34 +1,113 @@
-use crate::parse::{ItemRange, Language, ParseItem};
+use super::C_LIKE_TRIVIAL_TOKENS;
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
 
-/// Java syntax doesn't have escaped ranges other
-/// than simple string
+/// Matches `ch`, or the unicode escape of it like `\u0022` or `\uu0022` for `"`,
+/// as javac translates unicode escapes before lexing
+fn char_or_unicode_escape(src: &str, ch: char) -> Option<&str> {
+    if src.starts_with(ch) {
+        return Some(&src[..ch.len_utf8()]);
+    }
+    let rest = src.strip_prefix('\\')?;
+    let hex = rest.trim_start_matches('u');
+    let code = hex
+        .get(..4)
+        .and_then(|code| u32::from_str_radix(code, 16).ok());
+    (hex.len() < rest.len() && code == Some(ch as u32)).then(|| &src[..src.len() - hex.len() + 4])
+}
+
+const QUOTE: Matcher = Matcher::Fn(&(|src| char_or_unicode_escape(src, '"')));
+
+const APOSTROPHE: Matcher = Matcher::Fn(&(|src| char_or_unicode_escape(src, '\'')));
+
+/// Escapes in strings and char literals, eg. `\"` or `\\`, but not unicode escapes
+/// which are translated before, so `"\u0022"` is an empty string followed by a quote
+const ESCAPE: &[ParseItem] = &[ParseItem::String(
+    ItemRange::start_matcher(
+        Matcher::Fn(
+            &(|src| {
+                let escaped = src.strip_prefix('\\')?.chars().next()?;
+                (escaped != 'u').then(|| &src[..1 + escaped.len_utf8()])
+            }),
+        ),
+        Matcher::Empty,
+        Matcher::Empty,
+    )
+    .end_matcher(Matcher::Empty, Matcher::Empty, Matcher::Empty),
+    false,
+)];
+
+/// Single line comments end at a line break, or the unicode escape of one
+const LINE_COMMENT_END: Matcher = Matcher::Fn(
+    &(|src| {
+        if src.starts_with('\n') {
+            Some("")
+        } else {
+            char_or_unicode_escape(src, '\n').or_else(|| char_or_unicode_escape(src, '\r'))
+        }
+    }),
+);
+
+/// The opening quotes of text blocks are followed by a line break
+const TEXT_BLOCK_START: Matcher = Matcher::Fn(
+    &(|src| {
+        let rest = src.strip_prefix("\"\"\"")?;
+        rest.trim_start_matches([' ', '\t', '\x0c'])
+            .starts_with(['\n', '\r'])
+            .then(|| &src[..3])
+    }),
+);
+
+/// Java, unicode escapes of quotes and line breaks work like the chars themselves,
+/// eg. a `\u000a` ends a single line comment
 pub struct Java;
 impl Language for Java {
-    const PARSE_ITEMS: &'static [ParseItem] = &[
-        // single line comment
-        ParseItem::UnEscaped(&ParseItem::Comment(
-            ItemRange::fixed_start("//").pre_fixed_end("\n"),
-            false,
-        )),
-        // multi-line comment
-        ParseItem::UnEscaped(&ParseItem::Comment(
-            ItemRange::fixed_start("/*").fixed_end("*/"),
-            false,
-        )),
-        // multi-line string (defined above simple string as
-        // parsing this has precedence over simple string(`".*"`))
-        ParseItem::UnEscaped(&ParseItem::String(
-            ItemRange::fixed_start("\"\"\"").fixed_end("\"\"\""),
-            false,
-        )),
-        // simple string
-        ParseItem::Escaped(&ParseItem::String(
-            ItemRange::fixed_start("\"").fixed_end("\""),
-            false,
-        )),
-    ];
-    fn is_meaningful_src(src: &str) -> bool {
-        !src.chars()
-            .all(|ch| char::is_whitespace(ch) || ch == '}' || ch == '{')
-    }
+    const PARSE_ITEMS: &'static [ParseItem] =
+        &[
+            // single line comment
+            ParseItem::UnEscaped(&ParseItem::Comment(
+                ItemRange::fixed_start("//").end_matcher(
+                    LINE_COMMENT_END,
+                    Matcher::Empty,
+                    Matcher::Empty,
+                ),
+                false,
+            )),
+            // multi-line comment
+            ParseItem::UnEscaped(&ParseItem::Comment(
+                ItemRange::fixed_start("/*").fixed_end("*/"),
+                false,
+            )),
+            // text block (defined above simple string as
+            // parsing this has precedence over simple string(`".*"`))
+            ParseItem::WithInner(
+                &ParseItem::UnEscaped(&ParseItem::String(
+                    ItemRange::start_matcher(TEXT_BLOCK_START, Matcher::Empty, Matcher::Empty)
+                        .fixed_end("\"\"\""),
+                    false,
+                )),
+                ESCAPE,
+            ),
+            // simple string
+            ParseItem::WithInner(
+                &ParseItem::UnEscaped(&ParseItem::String(
+                    ItemRange::start_matcher(QUOTE, Matcher::Empty, Matcher::Empty).end_matcher(
+                        QUOTE,
+                        Matcher::Empty,
+                        Matcher::Empty,
+                    ),
+                    false,
+                )),
+                ESCAPE,
+            ),
+            // char literal, eg. `'"'`
+            ParseItem::WithInner(
+                &ParseItem::UnEscaped(&ParseItem::String(
+                    ItemRange::start_matcher(APOSTROPHE, Matcher::Empty, Matcher::Empty)
+                        .end_matcher(APOSTROPHE, Matcher::Empty, Matcher::Empty),
+                    false,
+                )),
+                ESCAPE,
+            ),
+        ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = C_LIKE_TRIVIAL_TOKENS;
 }
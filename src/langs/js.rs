@@ -1,7 +1,8 @@
+use super::ecmascript::{REGEX, TEMPLATE_LITERAL};
+use super::C_LIKE_TRIVIAL_TOKENS;
 use crate::parse::{ItemRange, Language, ParseItem};
 
-// we don't parse raw strings atm here,
-// as generally they are meaningful
+/// JavaScript, template literals nest source within `${}`
 pub struct JS;
 impl Language for JS {
     const PARSE_ITEMS: &'static [ParseItem] = &[
@@ -21,5 +22,8 @@ impl Language for JS {
             ItemRange::fixed_start("/*").fixed_end("*/"),
             false,
         )),
+        TEMPLATE_LITERAL,
+        REGEX,
     ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = C_LIKE_TRIVIAL_TOKENS;
 }
@@ -1,3 +1,4 @@
+use super::C_LIKE_TRIVIAL_TOKENS;
 use crate::parse::{ItemRange, Language, ParseItem};
 
 // we don't parse raw strings atm here,
@@ -26,4 +27,5 @@ impl Language for JSX {
             false,
         )),
     ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = C_LIKE_TRIVIAL_TOKENS;
 }
@@ -0,0 +1,80 @@
+use super::CHAR_LITERAL;
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
+
+/// `$(expr)` and `$name` in strings and commands are source
+const JULIA_INTERPOLATION: &[ParseItem] = &[
+    ParseItem::SameAsSrcInterpolation(ItemRange::fixed_start("$(").fixed_end(")")),
+    ParseItem::SameAsSrcInterpolation(
+        ItemRange::start_matcher(
+            Matcher::Exact("$"),
+            Matcher::Fn(
+                &(|src| {
+                    src.starts_with(|ch: char| ch.is_alphabetic() || ch == '_')
+                        .then(|| {
+                            let end = src
+                                .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                                .unwrap_or(src.len());
+                            &src[..end]
+                        })
+                }),
+            ),
+            Matcher::Empty,
+        )
+        .end_matcher(Matcher::Empty, Matcher::Empty, Matcher::Empty),
+    ),
+];
+
+/// `'` right after an operand is the adjoint operator, eg. `x'` or `A[1]'`
+const CHAR_LITERAL_PRECEDED_BY: Matcher = Matcher::Fn(
+    &(|before| {
+        (!before.ends_with(|ch: char| ch.is_alphanumeric() || "_)]}'.".contains(ch))).then_some("")
+    }),
+);
+
+/// Julia, block comments nest
+pub struct Julia;
+impl Language for Julia {
+    const PARSE_ITEMS: &'static [ParseItem] =
+        &[
+            ParseItem::Nested(&ParseItem::UnEscaped(&ParseItem::Comment(
+                ItemRange::fixed_start("#=").fixed_end("=#"),
+                false,
+            ))),
+            ParseItem::UnEscaped(&ParseItem::Comment(
+                ItemRange::fixed_start("#").pre_fixed_end("\n"),
+                false,
+            )),
+            ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::fixed_start("\"\"\"").fixed_end("\"\"\""),
+                    false,
+                )),
+                JULIA_INTERPOLATION,
+            ),
+            ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::fixed_start("\"").fixed_end("\""),
+                    false,
+                )),
+                JULIA_INTERPOLATION,
+            ),
+            // commands
+            ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::fixed_start("`").fixed_end("`"),
+                    false,
+                )),
+                JULIA_INTERPOLATION,
+            ),
+            ParseItem::Preceded(
+                CHAR_LITERAL_PRECEDED_BY,
+                &ParseItem::UnEscaped(&ParseItem::String(
+                    ItemRange::start_matcher(CHAR_LITERAL, Matcher::Empty, Matcher::Empty)
+                        .end_matcher(Matcher::Empty, Matcher::Empty, Matcher::Empty),
+                    false,
+                )),
+            ),
+        ];
+    const TRIVIAL_TOKENS: &'static [&'static str] =
+        &["end", "{", "}", "(", ")", "[", "]", ";", ","];
+}
@@ -0,0 +1,60 @@
+use super::C_LIKE_TRIVIAL_TOKENS;
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
+
+/// `$name` and `${expr}` templates in strings are source
+const KOTLIN_TEMPLATES: &[ParseItem] = &[
+    ParseItem::SameAsSrcInterpolation(ItemRange::fixed_start("${").fixed_end("}")),
+    ParseItem::SameAsSrcInterpolation(
+        ItemRange::start_matcher(
+            Matcher::Exact("$"),
+            Matcher::Fn(
+                &(|src| {
+                    src.starts_with(|ch: char| ch.is_alphabetic() || ch == '_')
+                        .then(|| {
+                            let end = src
+                                .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                                .unwrap_or(src.len());
+                            &src[..end]
+                        })
+                }),
+            ),
+            Matcher::Empty,
+        )
+        .end_matcher(Matcher::Empty, Matcher::Empty, Matcher::Empty),
+    ),
+];
+
+/// Kotlin block comments nest, and raw strings don't have escapes
+/// but do have templates
+pub struct Kotlin;
+impl Language for Kotlin {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("//").pre_fixed_end("\n"),
+            false,
+        )),
+        ParseItem::Nested(&ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("/*").fixed_end("*/"),
+            false,
+        ))),
+        ParseItem::WithInner(
+            &ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::fixed_start("\"\"\"").fixed_end("\"\"\""),
+                false,
+            )),
+            KOTLIN_TEMPLATES,
+        ),
+        ParseItem::WithInner(
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("\"").fixed_end("\""),
+                false,
+            )),
+            KOTLIN_TEMPLATES,
+        ),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\'").fixed_end("\'"),
+            false,
+        )),
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = C_LIKE_TRIVIAL_TOKENS;
+}
@@ -0,0 +1,48 @@
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
+
+/// Lua long brackets are keyed by the count of `=` in them, eg. `[==[ ]] ]==]`
+pub struct Lua;
+impl Language for Lua {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::start_matcher(
+                Matcher::Exact("--["),
+                Matcher::Repeat("="),
+                Matcher::Exact("["),
+            )
+            .end_matcher(
+                Matcher::Exact("]"),
+                Matcher::Repeat("="),
+                Matcher::Exact("]"),
+            ),
+            true,
+        )),
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("--").pre_fixed_end("\n"),
+            false,
+        )),
+        ParseItem::UnEscaped(&ParseItem::String(
+            ItemRange::start_matcher(
+                Matcher::Exact("["),
+                Matcher::Repeat("="),
+                Matcher::Exact("["),
+            )
+            .end_matcher(
+                Matcher::Exact("]"),
+                Matcher::Repeat("="),
+                Matcher::Exact("]"),
+            ),
+            true,
+        )),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\"").fixed_end("\""),
+            false,
+        )),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\'").fixed_end("\'"),
+            false,
+        )),
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] =
+        &["{", "}", "(", ")", "[", "]", ";", ",", "end"];
+}
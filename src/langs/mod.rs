@@ -1,17 +1,80 @@
 mod c;
+mod cpp;
 mod csharp;
+mod css;
+mod dart;
+mod ecmascript;
+mod elixir;
+mod erlang;
+mod go;
+mod haskell;
+mod html;
 mod java;
 mod js;
 mod jsx;
+mod julia;
+mod kotlin;
+mod lua;
+mod perl;
+mod php;
+mod preprocessor;
 mod python;
+mod r;
 mod ruby;
 mod rust;
 mod scala;
+mod shell;
+mod sql;
+mod swift;
+mod typescript;
 
 pub use java::Java;
 pub use c::C;
+pub use cpp::Cpp;
 pub use csharp::Csharp;
+pub use css::{Css, Scss};
+pub use dart::Dart;
+pub use elixir::Elixir;
+pub use erlang::Erlang;
+pub use go::Go;
+pub use haskell::Haskell;
+pub use html::{Html, Svelte, Vue};
+pub use js::JS;
+pub use jsx::JSX;
+pub use julia::Julia;
+pub use kotlin::Kotlin;
+pub use lua::Lua;
+pub use perl::Perl;
+pub use php::Php;
+pub use preprocessor::Preprocessor;
 pub use python::Python;
+pub use r::R;
 pub use ruby::Ruby;
 pub use rust::Rust;
 pub use scala::Scala;
+pub use shell::Shell;
+pub use sql::{MySql, Postgres, Sql, TSql};
+pub use swift::Swift;
+pub use typescript::{Tsx, TypeScript};
+
+use crate::parse::Matcher;
+
+/// Brackets and separators which lines like `});` or `],` are made up of in C like
+/// languages. Lines like `} else {` are deliberately left meaningful, as the
+/// keyword on them decides which branch runs.
+pub(crate) const C_LIKE_TRIVIAL_TOKENS: &[&str] = &["{", "}", "(", ")", "[", "]", ";", ","];
+
+/// Matches a whole quoted char literal, eg. `'a'`, `'\''` or `'\x41'`, but not
+/// the `'` of names like `'Just` in Haskell or `'sym` in Scala
+pub(crate) const CHAR_LITERAL: Matcher = Matcher::Fn(
+    &(|src| {
+        let rest = src.strip_prefix('\'')?;
+        let len = if let Some(escape) = rest.strip_prefix('\\') {
+            let first = escape.chars().next()?.len_utf8();
+            1 + first + escape[first..].find('\'')?
+        } else {
+            rest.chars().next().filter(|&ch| ch != '\'')?.len_utf8()
+        };
+        rest[len..].starts_with('\'').then(|| &src[..len + 2])
+    }),
+);
@@ -0,0 +1,286 @@
+use super::C_LIKE_TRIVIAL_TOKENS;
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
+
+const IDENTIFIER: Matcher = Matcher::Fn(
+    &(|src| {
+        src.starts_with(|ch: char| ch.is_alphabetic() || ch == '_')
+            .then(|| {
+                let end = src
+                    .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                    .unwrap_or(src.len());
+                &src[..end]
+            })
+    }),
+);
+
+/// `$#array` and `$#{$ref}` are the last index of an array, not comments
+const COMMENT_PRECEDED_BY: Matcher =
+    Matcher::Fn(&(|before| (!before.ends_with('$')).then_some("")));
+
+fn is_delimiter(ch: char) -> bool {
+    !(ch.is_alphanumeric() || ch.is_whitespace() || "_)]}>,;=".contains(ch))
+}
+
+fn closing_delimiter(open: char) -> char {
+    match open {
+        '(' => ')',
+        '[' => ']',
+        '{' => '}',
+        '<' => '>',
+        _ => open,
+    }
+}
+
+/// Length of the quoted text up to and including the `close` delimiter,
+/// brackets nest, eg. `a{b}c}` for `{`
+fn quoted_len(src: &str, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut chars = src.char_indices();
+    while let Some((i, ch)) = chars.next() {
+        if ch == '\\' {
+            chars.next();
+        } else if ch == close && depth == 0 {
+            return Some(i + ch.len_utf8());
+        } else if ch == close {
+            depth -= 1;
+        } else if ch == open {
+            depth += 1;
+        }
+    }
+    None
+}
+
+/// `q`, `qq`, `qw`, `qx`, `qr` and `m` quote what follows them, `s`, `tr` and `y` quote
+/// two parts of which the first is taken with the operator, eg. `s/a` of `s/a/b/`
+/// and `s{a} ` of `s{a} {b}`
+const QUOTE_LIKE_START: Matcher = Matcher::Fn(
+    &(|src| {
+        let len = src
+            .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+            .unwrap_or(src.len());
+        let (operator, rest) = src.split_at(len);
+        let open = rest.chars().next().filter(|&ch| is_delimiter(ch))?;
+        match operator {
+            "q" | "qq" | "qw" | "qx" | "qr" | "m" => Some(operator),
+            "s" | "tr" | "y" => {
+                let close = closing_delimiter(open);
+                let end =
+                    len + open.len_utf8() + quoted_len(&rest[open.len_utf8()..], open, close)?;
+                if open == close {
+                    // the delimiter between the parts begins the second one
+                    Some(&src[..end - close.len_utf8()])
+                } else {
+                    Some(&src[..src.len() - src[end..].trim_start().len()])
+                }
+            }
+            _ => None,
+        }
+    }),
+);
+
+/// Operators are words on their own, eg. not `$s`, `%q`, `Foo::y` or `$obj->m`
+const QUOTE_LIKE_PRECEDED_BY: Matcher = Matcher::Fn(
+    &(|before| {
+        (!(before.ends_with(|ch: char| ch.is_alphanumeric() || "_$@%&*:".contains(ch))
+            || before.ends_with("->")))
+        .then_some("")
+    }),
+);
+
+/// Delimiters which aren't brackets end the quote with the same char, eg. `q|a|`
+const QUOTE_LIKE_DELIMITER: Matcher = Matcher::Fn(
+    &(|src| {
+        src.chars()
+            .next()
+            .filter(|&ch| is_delimiter(ch) && !"([{<".contains(ch))
+            .map(|ch| &src[..ch.len_utf8()])
+    }),
+);
+
+/// Words after which a `/` begins a match instead of a division, eg. `split /,/`
+const REGEX_KEYWORDS: &[&str] = &[
+    "split", "grep", "map", "if", "elsif", "unless", "while", "until", "and", "or", "not",
+    "return", "when",
+];
+
+/// A bare `/` begins a match after an operator or punctuation, or after a keyword,
+/// eg. `$x =~ /a/` and `split /,/` but not `$a / $b` or `$h{a} / 2`
+const REGEX_PRECEDED_BY: Matcher = Matcher::Fn(
+    &(|before| {
+        let before = before.trim_end();
+        let is_operator = before.is_empty()
+            || before.ends_with([
+                '(', ',', '=', '~', '!', '{', ';', '?', ':', '&', '|', '[', '<', '>',
+            ]);
+        let is_keyword = || {
+            REGEX_KEYWORDS.iter().any(|kw| {
+                before.ends_with(kw)
+                    && !before[..before.len() - kw.len()]
+                        .ends_with(|ch: char| ch.is_alphanumeric() || "_$@%&:>".contains(ch))
+            })
+        };
+        (is_operator || is_keyword()).then_some("")
+    }),
+);
+
+/// A whole match on a single line with its flags, eg. `/[/"]+/gi` or `//`
+const REGEX_LITERAL: Matcher = Matcher::Fn(
+    &(|src| {
+        let rest = src.strip_prefix('/')?;
+        let (mut escape, mut class) = (false, false);
+        let end = rest
+            .char_indices()
+            .find(|&(_, ch)| {
+                match ch {
+                    _ if escape => escape = false,
+                    '\\' => escape = true,
+                    '[' => class = true,
+                    ']' => class = false,
+                    '/' if !class => return true,
+                    _ => {}
+                }
+                false
+            })
+            .map(|(i, _)| i + 2)
+            .filter(|&i| !src[..i].contains('\n'))?;
+        let flags = src[end..]
+            .find(|ch: char| !ch.is_ascii_alphabetic())
+            .unwrap_or(src.len() - end);
+        Some(&src[..end + flags])
+    }),
+);
+
+/// Perl 5, interpolation in strings isn't parsed as source
+pub struct Perl;
+impl Language for Perl {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        // POD, eg. `=pod` or `=head1` till a `=cut` line
+        ParseItem::LineStart(&ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::start_matcher(Matcher::Exact("="), IDENTIFIER, Matcher::Empty).end_matcher(
+                Matcher::Exact("\n=cut"),
+                Matcher::Fn(&(|src| Some(&src[..src.find('\n').unwrap_or(src.len())]))),
+                Matcher::Empty,
+            ),
+            false,
+        ))),
+        // everything after `__END__` or `__DATA__` is data
+        ParseItem::LineStart(&ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::start_matcher(
+                Matcher::Fn(
+                    &(|src| {
+                        let rest = src
+                            .strip_prefix("__END__")
+                            .or_else(|| src.strip_prefix("__DATA__"))?;
+                        (rest.is_empty() || rest.starts_with(['\n', '\r']))
+                            .then(|| &src[..src.len() - rest.len()])
+                    }),
+                ),
+                Matcher::Empty,
+                Matcher::Empty,
+            )
+            .end_matcher(
+                Matcher::Fn(&(|src| src.is_empty().then_some(""))),
+                Matcher::Empty,
+                Matcher::Empty,
+            ),
+            false,
+        ))),
+        ParseItem::Preceded(
+            COMMENT_PRECEDED_BY,
+            &ParseItem::UnEscaped(&ParseItem::Comment(
+                ItemRange::fixed_start("#").pre_fixed_end("\n"),
+                false,
+            )),
+        ),
+        // heredocs, `<<EOF`, `<<~"EOF"` etc. till a line with just `EOF`,
+        // the rest of the line they begin on is a part of the heredoc
+        ParseItem::UnEscaped(&ParseItem::String(
+            ItemRange::start_matcher(
+                Matcher::Fn(
+                    &(|src| {
+                        let rest = src.strip_prefix("<<")?;
+                        let rest = rest.strip_prefix('~').unwrap_or(rest);
+                        let rest = rest.strip_prefix(['"', '\'', '`']).unwrap_or(rest);
+                        Some(&src[..src.len() - rest.len()])
+                    }),
+                ),
+                IDENTIFIER,
+                Matcher::Fn(&(|src| Some(&src[..src.find('\n').unwrap_or(src.len())]))),
+            )
+            .end_matcher(
+                Matcher::Fn(
+                    &(|src| {
+                        let rest = src.strip_prefix('\n')?.trim_start_matches([' ', '\t']);
+                        Some(&src[..src.len() - rest.len()])
+                    }),
+                ),
+                IDENTIFIER,
+                Matcher::Fn(&(|src| (src.is_empty() || src.starts_with('\n')).then_some(""))),
+            ),
+            true,
+        )),
+        // quote like operators, eg. `qw(a (b))`, `m{a}x`, `q|c|` and `s/a/b/g`
+        ParseItem::Preceded(
+            QUOTE_LIKE_PRECEDED_BY,
+            &ParseItem::Nested(&ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(QUOTE_LIKE_START, Matcher::Empty, Matcher::Exact("("))
+                    .fixed_end(")"),
+                false,
+            ))),
+        ),
+        ParseItem::Preceded(
+            QUOTE_LIKE_PRECEDED_BY,
+            &ParseItem::Nested(&ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(QUOTE_LIKE_START, Matcher::Empty, Matcher::Exact("["))
+                    .fixed_end("]"),
+                false,
+            ))),
+        ),
+        ParseItem::Preceded(
+            QUOTE_LIKE_PRECEDED_BY,
+            &ParseItem::Nested(&ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(QUOTE_LIKE_START, Matcher::Empty, Matcher::Exact("{"))
+                    .fixed_end("}"),
+                false,
+            ))),
+        ),
+        ParseItem::Preceded(
+            QUOTE_LIKE_PRECEDED_BY,
+            &ParseItem::Nested(&ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(QUOTE_LIKE_START, Matcher::Empty, Matcher::Exact("<"))
+                    .fixed_end(">"),
+                false,
+            ))),
+        ),
+        ParseItem::Preceded(
+            QUOTE_LIKE_PRECEDED_BY,
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(QUOTE_LIKE_START, QUOTE_LIKE_DELIMITER, Matcher::Empty)
+                    .end_matcher(Matcher::Empty, QUOTE_LIKE_DELIMITER, Matcher::Empty),
+                true,
+            )),
+        ),
+        // bare matches, eg. `$x =~ /"/` or `split /'/`
+        ParseItem::Preceded(
+            REGEX_PRECEDED_BY,
+            &ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::start_matcher(REGEX_LITERAL, Matcher::Empty, Matcher::Empty)
+                    .end_matcher(Matcher::Empty, Matcher::Empty, Matcher::Empty),
+                false,
+            )),
+        ),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\"").fixed_end("\""),
+            false,
+        )),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\'").fixed_end("\'"),
+            false,
+        )),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("`").fixed_end("`"),
+            false,
+        )),
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = C_LIKE_TRIVIAL_TOKENS;
+}
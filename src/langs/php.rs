@@ -0,0 +1,216 @@
+use std::ops::Range;
+
+use super::html::embedded_script_or_style;
+use super::Html;
+use crate::count::blank_source;
+use crate::parse::{
+    get_embedded_regions, Composite, Embedded, ItemRange, Language, Matcher, ParseItem,
+};
+
+/// Inline html begins at the start of the source or after `?>`, leaving
+/// out the whitespace in between so that the `?>` line isn't counted for it
+const INLINE_HTML_PRECEDED_BY: Matcher = Matcher::Fn(
+    &(|before| {
+        let before = before.trim_end();
+        (before.is_empty() || before.ends_with("?>")).then_some("")
+    }),
+);
+
+const INLINE_HTML_START: Matcher = Matcher::Fn(
+    &(|src| {
+        (!src.starts_with(char::is_whitespace) && !src.is_empty() && !src.starts_with("<?"))
+            .then_some("")
+    }),
+);
+
+/// Inline html ends where php begins again, or with the source
+const INLINE_HTML_END: Matcher =
+    Matcher::Fn(&(|src| (src.is_empty() || src.starts_with("<?")).then_some("")));
+
+/// Single line comments end at the end of the line or at `?>`
+const LINE_COMMENT_END: Matcher = Matcher::Fn(
+    &(|src| (src.is_empty() || src.starts_with('\n') || src.starts_with("?>")).then_some("")),
+);
+
+const IDENTIFIER: Matcher = Matcher::Fn(
+    &(|src| {
+        src.starts_with(|ch: char| ch.is_alphabetic() || ch == '_')
+            .then(|| {
+                let end = src
+                    .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                    .unwrap_or(src.len());
+                &src[..end]
+            })
+    }),
+);
+
+/// `{$expr}`, `${expr}` and `$name` in double quoted strings are source
+const PHP_INTERPOLATION: &[ParseItem] = &[
+    ParseItem::SameAsSrcInterpolation(ItemRange::fixed_start("{$").fixed_end("}")),
+    ParseItem::SameAsSrcInterpolation(ItemRange::fixed_start("${").fixed_end("}")),
+    ParseItem::SameAsSrcInterpolation(
+        ItemRange::start_matcher(Matcher::Exact("$"), IDENTIFIER, Matcher::Empty).end_matcher(
+            Matcher::Empty,
+            Matcher::Empty,
+            Matcher::Empty,
+        ),
+    ),
+];
+
+/// Php, inline html outside of `<?php` and `?>` is output as is like a string
+/// and isn't meaningful, use it as a [`Composite`] to count the inline html separately
+pub struct Php;
+impl Language for Php {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        // inline html before the first `<?php` and after `?>`
+        ParseItem::Preceded(
+            INLINE_HTML_PRECEDED_BY,
+            &ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::start_matcher(INLINE_HTML_START, Matcher::Empty, Matcher::Empty)
+                    .end_matcher(INLINE_HTML_END, Matcher::Empty, Matcher::Empty),
+                false,
+            )),
+        ),
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("//").end_matcher(
+                LINE_COMMENT_END,
+                Matcher::Empty,
+                Matcher::Empty,
+            ),
+            false,
+        )),
+        // `#[` begins an attribute
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::start_matcher(
+                Matcher::Fn(
+                    &(|src| (src.starts_with('#') && !src.starts_with("#[")).then(|| &src[..1])),
+                ),
+                Matcher::Empty,
+                Matcher::Empty,
+            )
+            .end_matcher(LINE_COMMENT_END, Matcher::Empty, Matcher::Empty),
+            false,
+        )),
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("/*").fixed_end("*/"),
+            false,
+        )),
+        // heredoc and nowdoc, `<<<"ID"` or `<<<'ID'` till a line with `ID`
+        ParseItem::UnEscaped(&ParseItem::String(
+            ItemRange::start_matcher(
+                Matcher::Fn(
+                    &(|src| {
+                        let rest = src.strip_prefix("<<<")?.trim_start_matches([' ', '\t']);
+                        let rest = rest.strip_prefix(['"', '\'']).unwrap_or(rest);
+                        Some(&src[..src.len() - rest.len()])
+                    }),
+                ),
+                IDENTIFIER,
+                Matcher::Fn(
+                    &(|src| {
+                        let quote = if src.starts_with(['"', '\'']) { 1 } else { 0 };
+                        src[quote..].starts_with('\n').then(|| &src[..quote])
+                    }),
+                ),
+            )
+            .end_matcher(
+                Matcher::Fn(
+                    &(|src| {
+                        let rest = src.strip_prefix('\n')?.trim_start_matches([' ', '\t']);
+                        Some(&src[..src.len() - rest.len()])
+                    }),
+                ),
+                IDENTIFIER,
+                Matcher::Empty,
+            ),
+            true,
+        )),
+        ParseItem::WithInner(
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("\"").fixed_end("\""),
+                false,
+            )),
+            PHP_INTERPOLATION,
+        ),
+        // shell commands
+        ParseItem::WithInner(
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("`").fixed_end("`"),
+                false,
+            )),
+            PHP_INTERPOLATION,
+        ),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\'").fixed_end("\'"),
+            false,
+        )),
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] =
+        &["{", "}", "(", ")", "[", "]", ";", ",", "<?php", "<?", "?>"];
+}
+
+/// Finds the end of the php block that begins at `from`, skipping over
+/// `?>` in strings and block comments
+fn php_block_end(src: &str, from: usize) -> usize {
+    let bytes = src.as_bytes();
+    let mut i = from;
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+        if rest.starts_with(b"?>") {
+            return i + 2;
+        } else if matches!(rest[0], b'"' | b'\'' | b'`') {
+            let quote = rest[0];
+            i += 1;
+            while i < bytes.len() && bytes[i] != quote {
+                i += if bytes[i] == b'\\' { 2 } else { 1 };
+            }
+        } else if rest.starts_with(b"/*") {
+            i = src[i..].find("*/").map_or(src.len(), |e| i + e + 1);
+        } else if rest.starts_with(b"//") || (rest[0] == b'#' && !rest.starts_with(b"#[")) {
+            // single line comments end at `?>` as well
+            while i < bytes.len() && bytes[i] != b'\n' && !bytes[i..].starts_with(b"?>") {
+                i += 1;
+            }
+            continue;
+        }
+        i += 1;
+    }
+    src.len()
+}
+
+/// Spans of the php blocks, from `<?php` or `<?=` till `?>`
+fn php_blocks(src: &str) -> Vec<Range<usize>> {
+    let mut blocks = vec![];
+    let mut index = 0;
+    while let Some(start) = src[index..].find("<?").map(|i| i + index) {
+        // `<?xml` declarations aren't php
+        if src[start..].starts_with("<?xml") {
+            index = start + 2;
+            continue;
+        }
+        let end = php_block_end(src, start + 2);
+        blocks.push(start..end);
+        index = end;
+    }
+    blocks
+}
+
+/// Php files as html with php blocks, along with the `<script>` and `<style>` in the html
+impl Composite for Php {
+    const HOST: Embedded = Embedded::of::<Html>("html");
+    fn embedded(tag: &str, attributes: &str) -> Option<Embedded> {
+        embedded_script_or_style(tag, attributes)
+    }
+    fn embedded_regions(src: &str) -> Vec<(Embedded, Range<usize>)> {
+        let blocks = php_blocks(src);
+        let html = blank_source(src, &blocks, false);
+        let mut regions = get_embedded_regions::<Self>(&html);
+        regions.extend(
+            blocks
+                .into_iter()
+                .map(|block| (Embedded::of::<Php>("php"), block)),
+        );
+        regions.sort_by_key(|(_, span)| span.start);
+        regions
+    }
+}
@@ -0,0 +1,491 @@
+//! Evaluation of C style preprocessor conditionals, used to find the
+//! regions of a source that a compiler would never see.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::parse::{ItemRange, Language, Matcher, ParseItem, ParseOutput};
+
+/// A preprocessor directive, `#` must be the first thing on the line
+/// and the directive continues on the next line if escaped with `\`
+pub(crate) const DIRECTIVE: ParseItem =
+    ParseItem::LineStart(&ParseItem::Escaped(&ParseItem::Preprocessor(
+        ItemRange::start_matcher(
+            Matcher::Fn(
+                &(|src| {
+                    let indent = src.len() - src.trim_start_matches([' ', '\t']).len();
+                    src[indent..].starts_with('#').then(|| &src[..=indent])
+                }),
+            ),
+            Matcher::Empty,
+            Matcher::Empty,
+        )
+        .pre_fixed_end("\n"),
+        false,
+    )));
+
+/// Evaluates `#if`, `#ifdef`, `#elif`, etc. against a set of macros to find the
+/// spans of source that are compiled out.
+///
+/// By default nothing is known about the build configuration, so only conditions
+/// that don't depend on it (eg. `#if 0`) are decided and every other branch is
+/// assumed to be live. Once macros are provided, with [`Preprocessor::define`] or
+/// [`Preprocessor::with_defines`], every other macro is treated as undefined, the
+/// same way a compiler would.
+///
+/// usage:
+/// ```rust
+/// use dracula::count::get_meaningful_line_indices;
+/// use dracula::langs::{Preprocessor, C};
+///
+/// let src = "#ifdef DEBUG\nlog();\n#endif\n";
+/// let inactive_spans = Preprocessor::new().define("NDEBUG", "1").inactive_spans::<C>(src);
+/// let lines = get_meaningful_line_indices::<C>(src).with_inactive_spans(inactive_spans);
+/// assert_eq!(lines.flatten().collect::<Vec<_>>(), [0, 2]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Preprocessor {
+    defines: HashMap<String, String>,
+    exhaustive: bool,
+}
+
+impl Preprocessor {
+    /// Creates a [`Preprocessor`] which knows nothing about the build configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a [`Preprocessor`] where only the given macros are defined.
+    pub fn with_defines<I, K, V>(defines: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        Self {
+            defines: defines
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+            exhaustive: true,
+        }
+    }
+
+    /// Defines the macro `name` as `value`, making the set of macros exhaustive.
+    pub fn define(mut self, name: &str, value: &str) -> Self {
+        self.defines.insert(name.to_string(), value.to_string());
+        self.exhaustive = true;
+        self
+    }
+
+    /// Returns the sorted spans of `src` inside of conditional blocks that are
+    /// compiled out, the directives themselves are never a part of them.
+    pub fn inactive_spans<L: Language>(&self, src: &str) -> Vec<Range<usize>> {
+        let mut state = State {
+            macros: self
+                .defines
+                .iter()
+                .map(|(k, v)| (k.clone(), Some(v.clone())))
+                .collect(),
+            exhaustive: self.exhaustive,
+        };
+        let mut stack: Vec<Branch> = vec![];
+        let mut inactive_spans = vec![];
+        let mut inactive_since = None;
+        let mut offset = 0;
+        for po in L::get_parser(src) {
+            let start = offset;
+            offset += po.len();
+            let ParseOutput::Preprocessor(text) = po else {
+                continue;
+            };
+            let (directive, args) = split_directive(text);
+            let was_active = stack.last().is_none_or(|b| b.live != Some(false));
+            // directives of conditionals inside of compiled code are always compiled
+            let mut is_compiled = was_active;
+            match directive {
+                "if" | "ifdef" | "ifndef" => {
+                    let cond = if was_active {
+                        state.condition(directive, &args)
+                    } else {
+                        Some(false)
+                    };
+                    stack.push(Branch {
+                        parent_active: was_active,
+                        live: cond,
+                        taken: cond,
+                    });
+                }
+                "elif" | "elifdef" | "elifndef" | "else" => {
+                    if let Some(branch) = stack.last_mut().filter(|b| b.parent_active) {
+                        is_compiled = true;
+                        let cond = match branch.taken {
+                            Some(true) => Some(false),
+                            _ => state.condition(directive, &args),
+                        };
+                        branch.live = match branch.taken {
+                            Some(true) => Some(false),
+                            Some(false) => cond,
+                            None if cond == Some(false) => Some(false),
+                            None => None,
+                        };
+                        branch.taken = match (branch.taken, cond) {
+                            (Some(true), _) | (_, Some(true)) => Some(true),
+                            (Some(false), cond) => cond,
+                            (None, _) => None,
+                        };
+                    }
+                }
+                "endif" => {
+                    is_compiled |= stack.pop().is_some_and(|b| b.parent_active);
+                }
+                "define" if was_active => state.define(&args),
+                "undef" if was_active => state.undef(&args),
+                _ => {}
+            }
+            let is_active = stack.last().is_none_or(|b| b.live != Some(false));
+            if !was_active && is_compiled {
+                if let Some(since) = inactive_since.take() {
+                    inactive_spans.push(since..start);
+                }
+            }
+            if is_compiled && !is_active {
+                inactive_since = Some(start + text.len());
+            }
+        }
+        if let Some(since) = inactive_since {
+            inactive_spans.push(since..src.len());
+        }
+        inactive_spans
+    }
+}
+
+/// A single branch of a conditional block, `None` stands for not known
+#[derive(Debug)]
+struct Branch {
+    /// if the block containing this conditional is compiled
+    parent_active: bool,
+    /// if the current branch is compiled
+    live: Option<bool>,
+    /// if any of the branches so far was compiled
+    taken: Option<bool>,
+}
+
+/// Splits a directive into its name and its arguments,
+/// with comments and line continuations removed from the arguments
+fn split_directive(text: &str) -> (&str, String) {
+    let text = text.trim_start().trim_start_matches('#').trim_start();
+    let name_len = text
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(text.len());
+    let mut args = String::new();
+    let mut rest = text[name_len..].replace("\\\r\n", " ").replace("\\\n", " ");
+    while let Some(i) = rest.find("/*") {
+        args.push_str(&rest[..i]);
+        args.push(' ');
+        rest = rest[i + 2..]
+            .find("*/")
+            .map(|j| rest[i + 2 + j + 2..].to_string())
+            .unwrap_or_default();
+    }
+    args.push_str(rest.split("//").next().unwrap_or_default());
+    (&text[..name_len], args)
+}
+
+struct State {
+    /// `None` marks a macro that is known to be undefined
+    macros: HashMap<String, Option<String>>,
+    exhaustive: bool,
+}
+
+impl State {
+    fn define(&mut self, args: &str) {
+        let args = args.trim_start();
+        let name_len = identifier_len(args);
+        if name_len > 0 {
+            let value = if args[name_len..].starts_with('(') {
+                // function like macros can't be evaluated on their own
+                ""
+            } else {
+                args[name_len..].trim()
+            };
+            self.macros
+                .insert(args[..name_len].to_string(), Some(value.to_string()));
+        }
+    }
+
+    fn undef(&mut self, args: &str) {
+        let args = args.trim_start();
+        let name_len = identifier_len(args);
+        if name_len > 0 {
+            self.macros.insert(args[..name_len].to_string(), None);
+        }
+    }
+
+    /// Value of `defined(name)`
+    fn is_defined(&self, name: &str) -> Option<bool> {
+        match self.macros.get(name) {
+            Some(value) => Some(value.is_some()),
+            None if self.exhaustive => Some(false),
+            None => None,
+        }
+    }
+
+    fn condition(&self, directive: &str, args: &str) -> Option<bool> {
+        let args = args.trim();
+        let name = &args[..identifier_len(args)];
+        match directive {
+            "ifdef" | "elifdef" => self.is_defined(name),
+            "ifndef" | "elifndef" => self.is_defined(name).map(|d| !d),
+            "else" => Some(true),
+            _ => self.evaluate(args, 0).map(|v| v != 0),
+        }
+    }
+
+    fn evaluate(&self, expr: &str, depth: usize) -> Option<i64> {
+        let tokens = tokenize(expr)?;
+        let mut parser = ExprParser {
+            state: self,
+            tokens: &tokens,
+            pos: 0,
+            depth,
+        };
+        let value = parser.ternary();
+        if parser.pos != tokens.len() {
+            return None;
+        }
+        value
+    }
+}
+
+fn identifier_len(src: &str) -> usize {
+    if src.starts_with(|c: char| c.is_ascii_digit()) {
+        return 0;
+    }
+    src.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(src.len())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    Number(i64),
+    Ident(&'a str),
+    Op(&'a str),
+}
+
+const OPERATORS: &[&str] = &[
+    "||", "&&", "==", "!=", "<=", ">=", "<<", ">>", "|", "&", "^", "<", ">", "+", "-", "*", "/",
+    "%", "!", "~", "?", ":", "(", ")", ",",
+];
+
+fn tokenize(mut src: &str) -> Option<Vec<Token<'_>>> {
+    let mut tokens = vec![];
+    loop {
+        src = src.trim_start();
+        let Some(c) = src.chars().next() else {
+            return Some(tokens);
+        };
+        let len = if c.is_ascii_digit() {
+            let len = src
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(src.len());
+            let digits = src[..len].trim_end_matches(['u', 'U', 'l', 'L']);
+            let value = if let Some(hex) = digits
+                .strip_prefix("0x")
+                .or_else(|| digits.strip_prefix("0X"))
+            {
+                i64::from_str_radix(hex, 16).ok()?
+            } else if digits.len() > 1 && digits.starts_with('0') {
+                i64::from_str_radix(&digits[1..], 8).ok()?
+            } else {
+                digits.parse().ok()?
+            };
+            tokens.push(Token::Number(value));
+            len
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let len = identifier_len(src);
+            tokens.push(Token::Ident(&src[..len]));
+            len
+        } else {
+            // char literals and the like are not supported
+            let op = OPERATORS.iter().find(|op| src.starts_with(*op))?;
+            tokens.push(Token::Op(op));
+            op.len()
+        };
+        src = &src[len..];
+    }
+}
+
+/// Precedence climbing parser over the tokens of a `#if` expression,
+/// `None` stands for a value which depends on an unknown macro.
+struct ExprParser<'s, 't> {
+    state: &'s State,
+    tokens: &'t [Token<'t>],
+    pos: usize,
+    depth: usize,
+}
+
+const BINARY_OPERATORS: &[&[&str]] = &[
+    &["||"],
+    &["&&"],
+    &["|"],
+    &["^"],
+    &["&"],
+    &["==", "!="],
+    &["<", ">", "<=", ">="],
+    &["<<", ">>"],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+fn apply(op: &str, lhs: Option<i64>, rhs: Option<i64>) -> Option<i64> {
+    match op {
+        // short circuits don't care about the unknown side
+        "&&" => match (lhs, rhs) {
+            (Some(0), _) | (_, Some(0)) => Some(0),
+            (Some(_), Some(_)) => Some(1),
+            _ => None,
+        },
+        "||" => match (lhs, rhs) {
+            (Some(l), _) if l != 0 => Some(1),
+            (_, Some(r)) if r != 0 => Some(1),
+            (Some(_), Some(_)) => Some(0),
+            _ => None,
+        },
+        _ => {
+            let (l, r) = (lhs?, rhs?);
+            match op {
+                "|" => Some(l | r),
+                "^" => Some(l ^ r),
+                "&" => Some(l & r),
+                "==" => Some((l == r) as i64),
+                "!=" => Some((l != r) as i64),
+                "<" => Some((l < r) as i64),
+                ">" => Some((l > r) as i64),
+                "<=" => Some((l <= r) as i64),
+                ">=" => Some((l >= r) as i64),
+                "<<" => l.checked_shl(r.try_into().ok()?),
+                ">>" => l.checked_shr(r.try_into().ok()?),
+                "+" => l.checked_add(r),
+                "-" => l.checked_sub(r),
+                "*" => l.checked_mul(r),
+                "/" => l.checked_div(r),
+                _ => l.checked_rem(r),
+            }
+        }
+    }
+}
+
+impl<'t> ExprParser<'_, 't> {
+    fn peek(&self) -> Option<Token<'t>> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, op: &str) -> bool {
+        if self.peek() == Some(Token::Op(op)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn ternary(&mut self) -> Option<i64> {
+        let cond = self.binary(0);
+        if !self.eat("?") {
+            return cond;
+        }
+        let then = self.ternary();
+        if !self.eat(":") {
+            // invalid expression
+            self.pos = self.tokens.len() + 1;
+            return None;
+        }
+        let otherwise = self.ternary();
+        match cond {
+            Some(0) => otherwise,
+            Some(_) => then,
+            None if then == otherwise => then,
+            None => None,
+        }
+    }
+
+    fn binary(&mut self, level: usize) -> Option<i64> {
+        if level == BINARY_OPERATORS.len() {
+            return self.unary();
+        }
+        let mut lhs = self.binary(level + 1);
+        while let Some(&op) = BINARY_OPERATORS[level]
+            .iter()
+            .find(|op| self.peek() == Some(Token::Op(op)))
+        {
+            self.pos += 1;
+            let rhs = self.binary(level + 1);
+            lhs = apply(op, lhs, rhs);
+        }
+        lhs
+    }
+
+    fn unary(&mut self) -> Option<i64> {
+        let token = self.peek();
+        self.pos += 1;
+        match token? {
+            Token::Number(n) => Some(n),
+            Token::Op("!") => self.unary().map(|v| (v == 0) as i64),
+            Token::Op("~") => self.unary().map(|v| !v),
+            Token::Op("-") => self.unary().map(i64::wrapping_neg),
+            Token::Op("+") => self.unary(),
+            Token::Op("(") => {
+                let value = self.ternary();
+                if !self.eat(")") {
+                    self.pos = self.tokens.len() + 1;
+                }
+                value
+            }
+            Token::Ident("defined") => {
+                let parens = self.eat("(");
+                let name = match self.peek() {
+                    Some(Token::Ident(name)) => name,
+                    _ => {
+                        self.pos = self.tokens.len() + 1;
+                        return None;
+                    }
+                };
+                self.pos += 1;
+                if parens && !self.eat(")") {
+                    self.pos = self.tokens.len() + 1;
+                }
+                self.state.is_defined(name).map(i64::from)
+            }
+            Token::Ident(name) => {
+                if self.eat("(") {
+                    // function like macro invocations, eg. `__has_include(...)`
+                    let mut depth = 1;
+                    while depth > 0 && self.pos < self.tokens.len() {
+                        match self.tokens[self.pos] {
+                            Token::Op("(") => depth += 1,
+                            Token::Op(")") => depth -= 1,
+                            _ => {}
+                        }
+                        self.pos += 1;
+                    }
+                    return None;
+                }
+                match self.state.macros.get(name) {
+                    Some(Some(value)) if self.depth < 16 => {
+                        self.state.evaluate(value, self.depth + 1)
+                    }
+                    Some(Some(_)) => None,
+                    // identifiers which aren't macros evaluate to 0
+                    Some(None) => Some(0),
+                    None if self.state.exhaustive => Some(0),
+                    None => None,
+                }
+            }
+            Token::Op(_) => {
+                self.pos = self.tokens.len() + 1;
+                None
+            }
+        }
+    }
+}
@@ -1,111 +1,173 @@
-use crate::parse::{
-    Matcher, ParseItem, Language,ItemRange,
-};
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
 
-const PYTHON_STRING_START_MATCHER: Matcher = Matcher::Fn(
-    &(|src| {
-        if src.starts_with(['b', 'r', 'u']) {
-            if src[1..].starts_with(['b', 'r', 'u']) {
-                Some(&src[..2])
-            } else {
-                Some(&src[..1])
-            }
-        } else {
-            Some("")
-        }
-    }),
-);
+/// Matches the prefix of a string literal, when one of `prefixes` (in any case)
+/// is followed by a quote, eg. `Rb` in `Rb"..."`
+fn string_prefix<'a>(src: &'a str, prefixes: &[&str]) -> Option<&'a str> {
+    let len = src
+        .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+        .unwrap_or(src.len());
+    let prefix = &src[..len];
+    (src[len..].starts_with(['"', '\'']) && prefixes.iter().any(|p| p.eq_ignore_ascii_case(prefix)))
+        .then_some(prefix)
+}
+
+const PYTHON_STRING_START_MATCHER: Matcher =
+    Matcher::Fn(&(|src| string_prefix(src, &["", "r", "u", "b", "br", "rb"])));
 
-const PYTHON_FORMAT_STRING_START_MATCHER: Matcher = Matcher::Fn(
-    &(|src| {
-        if src.starts_with("fr") || src.starts_with("rf") {
-            Some(&src[..2])
-        } else if src.starts_with("f") {
-            Some(&src[..1])
-        } else {
-            None
-        }
-    }),
+/// f-strings and t-strings, which nest source within `{}`
+const PYTHON_FORMAT_STRING_START_MATCHER: Matcher =
+    Matcher::Fn(&(|src| string_prefix(src, &["f", "fr", "rf", "t", "tr", "rt"])));
+
+/// Prefixes are only prefixes at the start of a word
+const PYTHON_STRING_PRECEDED_BY: Matcher = Matcher::Fn(
+    &(|before| (!before.ends_with(|ch: char| ch.is_alphanumeric() || ch == '_')).then_some("")),
 );
 
+/// `{{` is a brace, `{expr}` is source which can have any string in it
+/// since python 3.12, eg. `f"{x["key"]}"`
+const PYTHON_FORMAT_FIELDS: &[ParseItem] = &[
+    ParseItem::InSource(
+        ItemRange::fixed_start("{{").end_matcher(Matcher::Empty, Matcher::Empty, Matcher::Empty),
+        false,
+    ),
+    ParseItem::SameAsSrcInterpolation(ItemRange::fixed_start("{").fixed_end("}")),
+];
+
 pub struct Python;
 impl Language for Python {
     const PARSE_ITEMS: &'static [ParseItem] = &[
-        ParseItem::UnEscaped(&ParseItem::String(
-            ItemRange::start_matcher(
-                PYTHON_STRING_START_MATCHER,
-                Matcher::Empty,
-                Matcher::Exact("\"\"\""),
-            )
-            .end_matcher(Matcher::Exact("\"\"\""), Matcher::Empty, Matcher::Empty),
-            false,
-        )),
-        ParseItem::UnEscaped(&ParseItem::String(
-            ItemRange::start_matcher(
-                PYTHON_STRING_START_MATCHER,
-                Matcher::Empty,
-                Matcher::Exact("'''"),
-            )
-            .end_matcher(Matcher::Exact("'''"), Matcher::Empty, Matcher::Empty),
-            false,
-        )),
-        ParseItem::UnEscaped(&ParseItem::InSource(
-            ItemRange::start_matcher(
-                PYTHON_FORMAT_STRING_START_MATCHER,
-                Matcher::Empty,
-                Matcher::Exact("\"\"\""),
-            )
-            .end_matcher(Matcher::Exact("\"\"\""), Matcher::Empty, Matcher::Empty),
-            false,
-        )),
-        ParseItem::UnEscaped(&ParseItem::InSource(
-            ItemRange::start_matcher(
-                PYTHON_FORMAT_STRING_START_MATCHER,
-                Matcher::Empty,
-                Matcher::Exact("'''"),
-            )
-            .end_matcher(Matcher::Exact("'''"), Matcher::Empty, Matcher::Empty),
-            false,
-        )),
+        ParseItem::Preceded(
+            PYTHON_STRING_PRECEDED_BY,
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(
+                    PYTHON_STRING_START_MATCHER,
+                    Matcher::Empty,
+                    Matcher::Exact("\"\"\""),
+                )
+                .end_matcher(
+                    Matcher::Exact("\"\"\""),
+                    Matcher::Empty,
+                    Matcher::Empty,
+                ),
+                false,
+            )),
+        ),
+        ParseItem::Preceded(
+            PYTHON_STRING_PRECEDED_BY,
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(
+                    PYTHON_STRING_START_MATCHER,
+                    Matcher::Empty,
+                    Matcher::Exact("'''"),
+                )
+                .end_matcher(Matcher::Exact("'''"), Matcher::Empty, Matcher::Empty),
+                false,
+            )),
+        ),
+        ParseItem::Preceded(
+            PYTHON_STRING_PRECEDED_BY,
+            &ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::InSource(
+                    ItemRange::start_matcher(
+                        PYTHON_FORMAT_STRING_START_MATCHER,
+                        Matcher::Empty,
+                        Matcher::Exact("\"\"\""),
+                    )
+                    .end_matcher(
+                        Matcher::Exact("\"\"\""),
+                        Matcher::Empty,
+                        Matcher::Empty,
+                    ),
+                    false,
+                )),
+                PYTHON_FORMAT_FIELDS,
+            ),
+        ),
+        ParseItem::Preceded(
+            PYTHON_STRING_PRECEDED_BY,
+            &ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::InSource(
+                    ItemRange::start_matcher(
+                        PYTHON_FORMAT_STRING_START_MATCHER,
+                        Matcher::Empty,
+                        Matcher::Exact("'''"),
+                    )
+                    .end_matcher(
+                        Matcher::Exact("'''"),
+                        Matcher::Empty,
+                        Matcher::Empty,
+                    ),
+                    false,
+                )),
+                PYTHON_FORMAT_FIELDS,
+            ),
+        ),
         ParseItem::UnEscaped(&ParseItem::Comment(
             ItemRange::fixed_start("#").pre_fixed_end("\n"),
             false,
         )),
-        ParseItem::Escaped(&ParseItem::String(
-            ItemRange::start_matcher(
-                PYTHON_STRING_START_MATCHER,
-                Matcher::Empty,
-                Matcher::Exact("\""),
-            )
-            .end_matcher(Matcher::Exact("\""), Matcher::Empty, Matcher::Empty),
-            false,
-        )),
-        ParseItem::Escaped(&ParseItem::String(
-            ItemRange::start_matcher(
-                PYTHON_STRING_START_MATCHER,
-                Matcher::Empty,
-                Matcher::Exact("'"),
-            )
-            .end_matcher(Matcher::Exact("'"), Matcher::Empty, Matcher::Empty),
-            false,
-        )),
-        ParseItem::Escaped(&ParseItem::InSource(
-            ItemRange::start_matcher(
-                PYTHON_FORMAT_STRING_START_MATCHER,
-                Matcher::Empty,
-                Matcher::Exact("\""),
-            )
-            .end_matcher(Matcher::Exact("\""), Matcher::Empty, Matcher::Empty),
-            false,
-        )),
-        ParseItem::Escaped(&ParseItem::InSource(
-            ItemRange::start_matcher(
-                PYTHON_FORMAT_STRING_START_MATCHER,
-                Matcher::Empty,
-                Matcher::Exact("'"),
-            )
-            .end_matcher(Matcher::Exact("'"), Matcher::Empty, Matcher::Empty),
-            false,
-        )),
+        ParseItem::Preceded(
+            PYTHON_STRING_PRECEDED_BY,
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(
+                    PYTHON_STRING_START_MATCHER,
+                    Matcher::Empty,
+                    Matcher::Exact("\""),
+                )
+                .end_matcher(Matcher::Exact("\""), Matcher::Empty, Matcher::Empty),
+                false,
+            )),
+        ),
+        ParseItem::Preceded(
+            PYTHON_STRING_PRECEDED_BY,
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(
+                    PYTHON_STRING_START_MATCHER,
+                    Matcher::Empty,
+                    Matcher::Exact("'"),
+                )
+                .end_matcher(Matcher::Exact("'"), Matcher::Empty, Matcher::Empty),
+                false,
+            )),
+        ),
+        ParseItem::Preceded(
+            PYTHON_STRING_PRECEDED_BY,
+            &ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::InSource(
+                    ItemRange::start_matcher(
+                        PYTHON_FORMAT_STRING_START_MATCHER,
+                        Matcher::Empty,
+                        Matcher::Exact("\""),
+                    )
+                    .end_matcher(
+                        Matcher::Exact("\""),
+                        Matcher::Empty,
+                        Matcher::Empty,
+                    ),
+                    false,
+                )),
+                PYTHON_FORMAT_FIELDS,
+            ),
+        ),
+        ParseItem::Preceded(
+            PYTHON_STRING_PRECEDED_BY,
+            &ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::InSource(
+                    ItemRange::start_matcher(
+                        PYTHON_FORMAT_STRING_START_MATCHER,
+                        Matcher::Empty,
+                        Matcher::Exact("'"),
+                    )
+                    .end_matcher(
+                        Matcher::Exact("'"),
+                        Matcher::Empty,
+                        Matcher::Empty,
+                    ),
+                    false,
+                )),
+                PYTHON_FORMAT_FIELDS,
+            ),
+        ),
     ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = &["(", ")", "[", "]", "{", "}", ","];
 }
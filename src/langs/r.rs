@@ -0,0 +1,79 @@
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
+
+/// `r"` or `R'` etc. at the start of a word
+const RAW_STRING_START: Matcher = Matcher::Fn(
+    &(|src| {
+        let rest = src.strip_prefix(['r', 'R'])?;
+        rest.starts_with(['"', '\'']).then(|| &src[..2])
+    }),
+);
+
+const RAW_STRING_PRECEDED_BY: Matcher = Matcher::Fn(
+    &(|before| {
+        (!before.ends_with(|ch: char| ch.is_alphanumeric() || ch == '_' || ch == '.')).then_some("")
+    }),
+);
+
+const QUOTE: Matcher = Matcher::Fn(&(|src| src.starts_with(['"', '\'']).then(|| &src[..1])));
+
+/// R, raw strings are keyed by the dashes around their brackets, eg. `r"-(a)")-"`
+/// is `a)"`. They end with either quote, whichever they began with.
+pub struct R;
+impl Language for R {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("#").pre_fixed_end("\n"),
+            false,
+        )),
+        ParseItem::Preceded(
+            RAW_STRING_PRECEDED_BY,
+            &ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::start_matcher(
+                    RAW_STRING_START,
+                    Matcher::Repeat("-"),
+                    Matcher::Exact("("),
+                )
+                .end_matcher(Matcher::Exact(")"), Matcher::Repeat("-"), QUOTE),
+                true,
+            )),
+        ),
+        ParseItem::Preceded(
+            RAW_STRING_PRECEDED_BY,
+            &ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::start_matcher(
+                    RAW_STRING_START,
+                    Matcher::Repeat("-"),
+                    Matcher::Exact("["),
+                )
+                .end_matcher(Matcher::Exact("]"), Matcher::Repeat("-"), QUOTE),
+                true,
+            )),
+        ),
+        ParseItem::Preceded(
+            RAW_STRING_PRECEDED_BY,
+            &ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::start_matcher(
+                    RAW_STRING_START,
+                    Matcher::Repeat("-"),
+                    Matcher::Exact("{"),
+                )
+                .end_matcher(Matcher::Exact("}"), Matcher::Repeat("-"), QUOTE),
+                true,
+            )),
+        ),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\"").fixed_end("\""),
+            false,
+        )),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\'").fixed_end("\'"),
+            false,
+        )),
+        // backtick quoted names, eg. `my var` or `+`
+        ParseItem::Escaped(&ParseItem::InSource(
+            ItemRange::fixed_start("`").fixed_end("`"),
+            false,
+        )),
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = &["{", "}", "(", ")", "[", "]", ","];
+}
@@ -1,37 +1,226 @@
-use crate::parse::{ItemRange, Language, ParseItem};
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
 
-// I don't think parsing interpolation in strings out
-// is a nice idea as they can be nested using interpolation
+/// `#{}` in double quoted strings, commands and most percent literals are source
+const RUBY_INTERPOLATION: &[ParseItem] = &[ParseItem::SameAsSrcInterpolation(
+    ItemRange::fixed_start("#{").fixed_end("}"),
+)];
+
+/// `%` with an optional type, eg. `%W` or `%Q`, begins a literal with interpolation
+/// when it isn't a modulo, that is not right after an operand like in `x%(y)`
+const PERCENT_START: Matcher = Matcher::Fn(
+    &(|src| {
+        let rest = src.strip_prefix('%')?;
+        let rest = rest.strip_prefix(['Q', 'W', 'I', 'r', 'x']).unwrap_or(rest);
+        Some(&src[..src.len() - rest.len()])
+    }),
+);
+
+/// `%q`, `%w`, `%i` and `%s` begin literals without interpolation
+const RAW_PERCENT_START: Matcher = Matcher::Fn(
+    &(|src| {
+        src.strip_prefix('%')?
+            .strip_prefix(['q', 'w', 'i', 's'])
+            .map(|_| &src[..2])
+    }),
+);
+
+const PERCENT_PRECEDED_BY: Matcher = Matcher::Fn(
+    &(|before| {
+        (!before.ends_with(|ch: char| ch.is_alphanumeric() || "_)]}".contains(ch))).then_some("")
+    }),
+);
+
+/// `%=` is the modulo assignment after an operand, even with spaces between them
+/// like in `i %= 3`, and only begins a literal at the beginning of an expression
+const PERCENT_EQUALS_PRECEDED_BY: Matcher = Matcher::Fn(
+    &(|before| {
+        (!before
+            .trim_end_matches([' ', '\t'])
+            .ends_with(|ch: char| ch.is_alphanumeric() || "_)]}".contains(ch)))
+        .then_some("")
+    }),
+);
+
+/// Delimiters which aren't brackets end the literal with the same char, eg. `%q|a|`,
+/// `%` followed by a space is always a modulo and `%=` is parsed on its own
+const PERCENT_DELIMITER: Matcher = Matcher::Fn(
+    &(|src| {
+        src.chars()
+            .next()
+            .filter(|ch| !(ch.is_alphanumeric() || ch.is_whitespace() || "([{<=".contains(*ch)))
+            .map(|ch| &src[..ch.len_utf8()])
+    }),
+);
+
+/// Ruby, interpolation in strings can nest strings, eg. `"#{"}"}"`,
+/// and brackets nest in percent literals, eg. `%w(a (b) c)`.
 pub struct Ruby;
 impl Language for Ruby {
     const PARSE_ITEMS: &'static [ParseItem] = &[
+        ParseItem::LineStart(&ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("=begin").fixed_end("\n=end"),
+            false,
+        ))),
+        // everything after `__END__` is data
+        ParseItem::LineStart(&ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::start_matcher(
+                Matcher::Fn(
+                    &(|src| {
+                        let rest = src.strip_prefix("__END__")?;
+                        (rest.is_empty() || rest.starts_with(['\n', '\r'])).then(|| &src[..7])
+                    }),
+                ),
+                Matcher::Empty,
+                Matcher::Empty,
+            )
+            .end_matcher(
+                Matcher::Fn(&(|src| src.is_empty().then_some(""))),
+                Matcher::Empty,
+                Matcher::Empty,
+            ),
+            false,
+        ))),
         ParseItem::UnEscaped(&ParseItem::Comment(
             ItemRange::fixed_start("#").pre_fixed_end("\n"),
             false,
         )),
-        // we need to implemented nested parsing for this,
-        // hence also having to clean up clean up the state machine code
-        // ParseItem::Escaped(&ParseItem::String(
-        //     ItemRange::fixed_start("\"").fixed_end("\""),
-        //     false,
-        // )),
-        // ParseItem::Escaped(&ParseItem::String(
-        //     ItemRange::fixed_start("\'").fixed_end("\'"),
-        //     false,
-        // )),
-        // wip new feature to support strings in ruby properly
-        // ParseItem::WithInner(
-        //     &ParseItem::Escaped(&ParseItem::String(
-        //         ItemRange::fixed_start("\"").fixed_end("\""),
-        //         false,
-        //     )),
-        //     &ParseItem::SameAsSrcInterpolation(
-        //         ItemRange::fixed_start("${").fixed_end("}"),
-        //     )
-        // ),
-        ParseItem::UnEscaped(&ParseItem::Comment(
-            ItemRange::fixed_start("\n=begin").fixed_end("\n=end"),
+        // symbols, eg. `:"a b"` and `:'c'`
+        ParseItem::WithInner(
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start(":\"").fixed_end("\""),
+                false,
+            )),
+            RUBY_INTERPOLATION,
+        ),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start(":\'").fixed_end("\'"),
+            false,
+        )),
+        ParseItem::WithInner(
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("\"").fixed_end("\""),
+                false,
+            )),
+            RUBY_INTERPOLATION,
+        ),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\'").fixed_end("\'"),
             false,
         )),
+        // commands
+        ParseItem::WithInner(
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("`").fixed_end("`"),
+                false,
+            )),
+            RUBY_INTERPOLATION,
+        ),
+        // percent literals, eg. `%w(a b)`, `%Q[#{x}]` and `%q|c|`
+        ParseItem::Preceded(
+            PERCENT_PRECEDED_BY,
+            &ParseItem::Nested(&ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(RAW_PERCENT_START, Matcher::Empty, Matcher::Exact("("))
+                    .fixed_end(")"),
+                false,
+            ))),
+        ),
+        ParseItem::Preceded(
+            PERCENT_PRECEDED_BY,
+            &ParseItem::Nested(&ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(RAW_PERCENT_START, Matcher::Empty, Matcher::Exact("["))
+                    .fixed_end("]"),
+                false,
+            ))),
+        ),
+        ParseItem::Preceded(
+            PERCENT_PRECEDED_BY,
+            &ParseItem::Nested(&ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(RAW_PERCENT_START, Matcher::Empty, Matcher::Exact("{"))
+                    .fixed_end("}"),
+                false,
+            ))),
+        ),
+        ParseItem::Preceded(
+            PERCENT_PRECEDED_BY,
+            &ParseItem::Nested(&ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(RAW_PERCENT_START, Matcher::Empty, Matcher::Exact("<"))
+                    .fixed_end(">"),
+                false,
+            ))),
+        ),
+        ParseItem::Preceded(
+            PERCENT_PRECEDED_BY,
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(RAW_PERCENT_START, PERCENT_DELIMITER, Matcher::Empty)
+                    .end_matcher(Matcher::Empty, PERCENT_DELIMITER, Matcher::Empty),
+                true,
+            )),
+        ),
+        ParseItem::Preceded(
+            PERCENT_PRECEDED_BY,
+            &ParseItem::WithInner(
+                &ParseItem::Nested(&ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::start_matcher(PERCENT_START, Matcher::Empty, Matcher::Exact("("))
+                        .fixed_end(")"),
+                    false,
+                ))),
+                RUBY_INTERPOLATION,
+            ),
+        ),
+        ParseItem::Preceded(
+            PERCENT_PRECEDED_BY,
+            &ParseItem::WithInner(
+                &ParseItem::Nested(&ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::start_matcher(PERCENT_START, Matcher::Empty, Matcher::Exact("["))
+                        .fixed_end("]"),
+                    false,
+                ))),
+                RUBY_INTERPOLATION,
+            ),
+        ),
+        ParseItem::Preceded(
+            PERCENT_PRECEDED_BY,
+            &ParseItem::WithInner(
+                &ParseItem::Nested(&ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::start_matcher(PERCENT_START, Matcher::Empty, Matcher::Exact("{"))
+                        .fixed_end("}"),
+                    false,
+                ))),
+                RUBY_INTERPOLATION,
+            ),
+        ),
+        ParseItem::Preceded(
+            PERCENT_PRECEDED_BY,
+            &ParseItem::WithInner(
+                &ParseItem::Nested(&ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::start_matcher(PERCENT_START, Matcher::Empty, Matcher::Exact("<"))
+                        .fixed_end(">"),
+                    false,
+                ))),
+                RUBY_INTERPOLATION,
+            ),
+        ),
+        ParseItem::Preceded(
+            PERCENT_PRECEDED_BY,
+            &ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::start_matcher(PERCENT_START, PERCENT_DELIMITER, Matcher::Empty)
+                        .end_matcher(Matcher::Empty, PERCENT_DELIMITER, Matcher::Empty),
+                    true,
+                )),
+                RUBY_INTERPOLATION,
+            ),
+        ),
+        ParseItem::Preceded(
+            PERCENT_EQUALS_PRECEDED_BY,
+            &ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::fixed_start("%=").fixed_end("="),
+                    false,
+                )),
+                RUBY_INTERPOLATION,
+            ),
+        ),
     ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = &["end", "(", ")", "[", "]", "{", "}", ","];
 }
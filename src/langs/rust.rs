@@ -31,9 +31,5 @@ impl Language for Rust {
             true,
         )),
     ];
-
-    fn is_meaningful_src(src: &str) -> bool {
-        !src.chars()
-            .all(|ch| char::is_whitespace(ch) || ch == '}' || ch == '{' || ch == '(' || ch == ')')
-    }
+    const TRIVIAL_TOKENS: &'static [&'static str] = &["{", "}", "(", ")"];
 }
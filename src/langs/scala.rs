@@ -1,5 +1,53 @@
-use crate::parse::{ItemRange, Language, ParseItem};
+use super::CHAR_LITERAL;
+use super::C_LIKE_TRIVIAL_TOKENS;
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
 
+const IDENTIFIER: Matcher = Matcher::Fn(
+    &(|src| {
+        src.starts_with(|ch: char| ch.is_alphabetic() || ch == '_')
+            .then(|| {
+                let end = src
+                    .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                    .unwrap_or(src.len());
+                &src[..end]
+            })
+    }),
+);
+
+/// Interpolators are identifiers right before the quotes, eg. `s`, `f`, `raw` or `json`
+const INTERPOLATOR_PRECEDED_BY: Matcher = Matcher::Fn(
+    &(|before| (!before.ends_with(|ch: char| ch.is_alphanumeric() || ch == '_')).then_some("")),
+);
+
+/// `$$` and `$"` are escapes, `${expr}` and `$name` are source
+const SCALA_INTERPOLATION: &[ParseItem] = &[
+    ParseItem::String(
+        ItemRange::start_matcher(
+            Matcher::Fn(
+                &(|src| (src.starts_with("$$") || src.starts_with("$\"")).then(|| &src[..2])),
+            ),
+            Matcher::Empty,
+            Matcher::Empty,
+        )
+        .end_matcher(Matcher::Empty, Matcher::Empty, Matcher::Empty),
+        false,
+    ),
+    ParseItem::SameAsSrcInterpolation(ItemRange::fixed_start("${").fixed_end("}")),
+    ParseItem::SameAsSrcInterpolation(
+        ItemRange::start_matcher(Matcher::Exact("$"), IDENTIFIER, Matcher::Empty).end_matcher(
+            Matcher::Empty,
+            Matcher::Empty,
+            Matcher::Empty,
+        ),
+    ),
+];
+
+/// Triple quoted strings end at the last of the quotes, eg. `"""a""""` is `a"`
+const TRIPLE_QUOTE_END: Matcher = Matcher::Fn(
+    &(|src| (src.starts_with("\"\"\"") && !src[3..].starts_with('"')).then(|| &src[..3])),
+);
+
+/// Scala 2 and 3, block comments nest and interpolated strings nest source
 pub struct Scala;
 impl Language for Scala {
     const PARSE_ITEMS: &'static [ParseItem] = &[
@@ -7,13 +55,53 @@ impl Language for Scala {
             ItemRange::fixed_start("//").pre_fixed_end("\n"),
             false,
         )),
-        ParseItem::UnEscaped(&ParseItem::Comment(
+        ParseItem::Nested(&ParseItem::UnEscaped(&ParseItem::Comment(
             ItemRange::fixed_start("/*").fixed_end("*/"),
             false,
+        ))),
+        ParseItem::Preceded(
+            INTERPOLATOR_PRECEDED_BY,
+            &ParseItem::WithInner(
+                &ParseItem::UnEscaped(&ParseItem::String(
+                    ItemRange::start_matcher(IDENTIFIER, Matcher::Empty, Matcher::Exact("\"\"\""))
+                        .end_matcher(TRIPLE_QUOTE_END, Matcher::Empty, Matcher::Empty),
+                    false,
+                )),
+                SCALA_INTERPOLATION,
+            ),
+        ),
+        ParseItem::Preceded(
+            INTERPOLATOR_PRECEDED_BY,
+            &ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::start_matcher(IDENTIFIER, Matcher::Empty, Matcher::Exact("\""))
+                        .fixed_end("\""),
+                    false,
+                )),
+                SCALA_INTERPOLATION,
+            ),
+        ),
+        ParseItem::UnEscaped(&ParseItem::String(
+            ItemRange::fixed_start("\"\"\"").end_matcher(
+                TRIPLE_QUOTE_END,
+                Matcher::Empty,
+                Matcher::Empty,
+            ),
+            false,
         )),
         ParseItem::Escaped(&ParseItem::String(
             ItemRange::fixed_start("\"").fixed_end("\""),
             false,
         )),
+        // char literals, symbols like `'sym` and quotes like `'{ x }` are source
+        ParseItem::UnEscaped(&ParseItem::String(
+            ItemRange::start_matcher(CHAR_LITERAL, Matcher::Empty, Matcher::Empty).end_matcher(
+                Matcher::Empty,
+                Matcher::Empty,
+                Matcher::Empty,
+            ),
+            false,
+        )),
     ];
-}
\ No newline at end of file
+    const TRIVIAL_TOKENS: &'static [&'static str] = C_LIKE_TRIVIAL_TOKENS;
+}
@@ -0,0 +1,162 @@
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
+
+/// `#` begins a comment only at the start of a word, so `$#` and `${#var}` aren't comments
+const COMMENT_PRECEDED_BY: Matcher = Matcher::Fn(
+    &(|before| {
+        (before.is_empty()
+            || before.ends_with(|ch: char| ch.is_whitespace() || ";&|()".contains(ch)))
+        .then_some("")
+    }),
+);
+
+/// Quotes escaped with a `\` outside of strings don't begin one, eg. `don\'t`
+const UNESCAPED_QUOTE: Matcher = Matcher::Fn(
+    &(|before| {
+        let backslashes = before.len() - before.trim_end_matches('\\').len();
+        (backslashes % 2 == 0).then_some("")
+    }),
+);
+
+const IDENTIFIER: Matcher = Matcher::Fn(
+    &(|src| {
+        src.starts_with(|ch: char| ch.is_alphabetic() || ch == '_')
+            .then(|| {
+                let end = src
+                    .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                    .unwrap_or(src.len());
+                &src[..end]
+            })
+    }),
+);
+
+/// `$(cmd)`, `${var}` and `` `cmd` `` in double quoted strings are source
+const SHELL_SUBSTITUTION: &[ParseItem] = &[
+    ParseItem::SameAsSrcInterpolation(ItemRange::fixed_start("$(").fixed_end(")")),
+    ParseItem::SameAsSrcInterpolation(ItemRange::fixed_start("${").fixed_end("}")),
+    ParseItem::SameAsSrcInterpolation(ItemRange::fixed_start("`").fixed_end("`")),
+];
+
+/// Matches the beginning of a heredoc up to its delimiter word, `<<-` if `dash`
+/// or else `<<`, eg. `<<'` of `<<'EOF'`
+fn heredoc_start(src: &str, dash: bool) -> Option<&str> {
+    let rest = src.strip_prefix("<<")?;
+    if rest.starts_with('<') || rest.starts_with('-') != dash {
+        return None;
+    }
+    let rest = rest.strip_prefix('-').unwrap_or(rest);
+    let rest = rest.trim_start_matches([' ', '\t']);
+    let rest = rest.strip_prefix(['"', '\'', '\\']).unwrap_or(rest);
+    Some(&src[..src.len() - rest.len()])
+}
+
+/// The closing quote of a quoted delimiter word, eg. `'` of `<<'EOF'`
+const HEREDOC_START_END: Matcher =
+    Matcher::Fn(&(|src| Some(&src[..usize::from(src.starts_with(['"', '\'']))])));
+
+/// Matches nothing at the end of a line or of the source
+const END_OF_LINE: Matcher =
+    Matcher::Fn(&(|src| (src.is_empty() || src.starts_with('\n')).then_some("")));
+
+/// Checks if `before` ends with the beginning of a heredoc, eg. `cat <<-'EOF'`
+const HEREDOC_OPENED: Matcher = Matcher::Fn(
+    &(|before| {
+        let rest = before.strip_suffix(['"', '\'']).unwrap_or(before);
+        let rest = rest.trim_end_matches(|ch: char| ch.is_alphanumeric() || ch == '_');
+        let rest = rest.strip_suffix(['"', '\'', '\\']).unwrap_or(rest);
+        let rest = rest.trim_end_matches([' ', '\t']);
+        let rest = rest.strip_suffix('-').unwrap_or(rest);
+        (rest.ends_with("<<") && !rest.ends_with("<<<")).then_some("")
+    }),
+);
+
+/// The rest of the line a heredoc begins on is source, eg. `| grep x` of
+/// `cat <<EOF | grep x`, the heredoc itself begins on the next line
+const HEREDOC_REST_OF_LINE: &[ParseItem] = &[ParseItem::Preceded(
+    HEREDOC_OPENED,
+    &ParseItem::SameAsSrcInterpolation(
+        ItemRange::start_matcher(
+            Matcher::Fn(&(|src| (!src.is_empty() && !src.starts_with('\n')).then_some(""))),
+            Matcher::Empty,
+            Matcher::Empty,
+        )
+        .end_matcher(END_OF_LINE, Matcher::Empty, Matcher::Empty),
+    ),
+)];
+
+/// Bourne like shells, eg. bash, sh and zsh
+pub struct Shell;
+impl Language for Shell {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        ParseItem::Preceded(
+            COMMENT_PRECEDED_BY,
+            &ParseItem::UnEscaped(&ParseItem::Comment(
+                ItemRange::fixed_start("#").pre_fixed_end("\n"),
+                false,
+            )),
+        ),
+        // heredocs, `<<EOF`, `<<'EOF'` etc. till a line with just `EOF`
+        ParseItem::WithInner(
+            &ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::start_matcher(
+                    Matcher::Fn(&(|src| heredoc_start(src, false))),
+                    IDENTIFIER,
+                    HEREDOC_START_END,
+                )
+                .end_matcher(Matcher::Exact("\n"), IDENTIFIER, END_OF_LINE),
+                true,
+            )),
+            HEREDOC_REST_OF_LINE,
+        ),
+        // `<<-EOF` heredocs, the line with `EOF` can be indented with tabs
+        ParseItem::WithInner(
+            &ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::start_matcher(
+                    Matcher::Fn(&(|src| heredoc_start(src, true))),
+                    IDENTIFIER,
+                    HEREDOC_START_END,
+                )
+                .end_matcher(
+                    Matcher::Fn(
+                        &(|src| {
+                            let rest = src.strip_prefix('\n')?.trim_start_matches('\t');
+                            Some(&src[..src.len() - rest.len()])
+                        }),
+                    ),
+                    IDENTIFIER,
+                    END_OF_LINE,
+                ),
+                true,
+            )),
+            HEREDOC_REST_OF_LINE,
+        ),
+        // ansi c strings, eg. `$'a\tb'`
+        ParseItem::Preceded(
+            UNESCAPED_QUOTE,
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("$'").fixed_end("\'"),
+                false,
+            )),
+        ),
+        ParseItem::Preceded(
+            UNESCAPED_QUOTE,
+            &ParseItem::WithInner(
+                &ParseItem::Escaped(&ParseItem::String(
+                    ItemRange::fixed_start("\"").fixed_end("\""),
+                    false,
+                )),
+                SHELL_SUBSTITUTION,
+            ),
+        ),
+        // no escapes in single quoted strings
+        ParseItem::Preceded(
+            UNESCAPED_QUOTE,
+            &ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::fixed_start("\'").fixed_end("\'"),
+                false,
+            )),
+        ),
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = &[
+        "{", "}", "(", ")", ";", "then", "else", "fi", "do", "done", "esac",
+    ];
+}
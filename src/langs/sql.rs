@@ -0,0 +1,260 @@
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
+
+const LINE_COMMENT: ParseItem = ParseItem::UnEscaped(&ParseItem::Comment(
+    ItemRange::fixed_start("--").pre_fixed_end("\n"),
+    false,
+));
+
+const BLOCK_COMMENT: ParseItem = ParseItem::UnEscaped(&ParseItem::Comment(
+    ItemRange::fixed_start("/*").fixed_end("*/"),
+    false,
+));
+
+/// `'it''s'` is lexed as two adjacent strings, which is the same as one
+const STRING: ParseItem = ParseItem::UnEscaped(&ParseItem::String(
+    ItemRange::fixed_start("\'").fixed_end("\'"),
+    false,
+));
+
+/// Identifiers like `"order"` are source, quotes and dashes in them shouldn't
+/// begin strings or comments
+const QUOTED_IDENTIFIER: ParseItem = ParseItem::UnEscaped(&ParseItem::InSource(
+    ItemRange::fixed_start("\"").fixed_end("\""),
+    false,
+));
+
+const NOT_AFTER_WORD: Matcher = Matcher::Fn(
+    &(|before| {
+        (!before.ends_with(|ch: char| ch.is_alphanumeric() || ch == '_' || ch == '$')).then_some("")
+    }),
+);
+
+/// The tag of `$tag$`, which may be empty but can't begin with a digit like `$1`
+fn dollar_quote_tag(src: &str) -> Option<&str> {
+    if src.starts_with(|ch: char| ch.is_ascii_digit()) {
+        return None;
+    }
+    let end = src
+        .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+        .unwrap_or(src.len());
+    Some(&src[..end])
+}
+
+const DOLLAR_QUOTE_TAG: Matcher = Matcher::Fn(&(|src| dollar_quote_tag(src)));
+
+/// Items in dollar quoted bodies are matched whole, as they can't be nested
+/// like the items of the language itself
+const fn whole(start: Matcher) -> ItemRange {
+    ItemRange::start_matcher(start, Matcher::Empty, Matcher::Empty).end_matcher(
+        Matcher::Empty,
+        Matcher::Empty,
+        Matcher::Empty,
+    )
+}
+
+/// Comments, strings and quotes in the bodies of functions, eg. `$fn$ ... $fn$`
+const DOLLAR_QUOTED_BODY: &[ParseItem] = &[
+    ParseItem::Comment(
+        whole(Matcher::Fn(
+            &(|src| {
+                src.starts_with("--")
+                    .then(|| &src[..src.find('\n').unwrap_or(src.len())])
+            }),
+        )),
+        false,
+    ),
+    // block comments nest
+    ParseItem::Comment(
+        whole(Matcher::Fn(
+            &(|src| {
+                let bytes = src.as_bytes();
+                let (mut depth, mut i) = (0usize, 0);
+                while i < bytes.len() {
+                    if bytes[i..].starts_with(b"/*") {
+                        depth += 1;
+                        i += 2;
+                    } else if depth > 0 && bytes[i..].starts_with(b"*/") {
+                        depth -= 1;
+                        i += 2;
+                        if depth == 0 {
+                            return Some(&src[..i]);
+                        }
+                    } else if depth == 0 {
+                        return None;
+                    } else {
+                        i += 1;
+                    }
+                }
+                None
+            }),
+        )),
+        false,
+    ),
+    ParseItem::Preceded(
+        NOT_AFTER_WORD,
+        &ParseItem::String(
+            whole(Matcher::Fn(
+                &(|src| {
+                    let tag = dollar_quote_tag(src.strip_prefix('$')?)?;
+                    let delimiter = src.get(..tag.len() + 2)?;
+                    if !delimiter.ends_with('$') {
+                        return None;
+                    }
+                    let end = src[delimiter.len()..].find(delimiter)?;
+                    Some(&src[..end + 2 * delimiter.len()])
+                }),
+            )),
+            false,
+        ),
+    ),
+    // escape strings, eg. `E'it\'s'`
+    ParseItem::Preceded(
+        NOT_AFTER_WORD,
+        &ParseItem::String(
+            whole(Matcher::Fn(
+                &(|src| {
+                    let rest = src.strip_prefix(['E', 'e'])?.strip_prefix('\'')?;
+                    let mut chars = rest.char_indices();
+                    while let Some((i, ch)) = chars.next() {
+                        match ch {
+                            '\\' => _ = chars.next(),
+                            '\'' => return Some(&src[..src.len() - rest.len() + i + 1]),
+                            _ => {}
+                        }
+                    }
+                    None
+                }),
+            )),
+            false,
+        ),
+    ),
+    ParseItem::String(
+        whole(Matcher::Fn(
+            &(|src| {
+                let end = src.strip_prefix('\'')?.find('\'')?;
+                Some(&src[..end + 2])
+            }),
+        )),
+        false,
+    ),
+    ParseItem::InSource(
+        whole(Matcher::Fn(
+            &(|src| {
+                let end = src.strip_prefix('"')?.find('"')?;
+                Some(&src[..end + 2])
+            }),
+        )),
+        false,
+    ),
+];
+
+/// Standard SQL, also works well enough for SQLite and other dialects
+pub struct Sql;
+impl Language for Sql {
+    const PARSE_ITEMS: &'static [ParseItem] =
+        &[LINE_COMMENT, BLOCK_COMMENT, STRING, QUOTED_IDENTIFIER];
+    const TRIVIAL_TOKENS: &'static [&'static str] = &["(", ")", ";", ","];
+}
+
+/// PostgreSQL, block comments nest and `$tag$ ... $tag$` quotes are keyed by the tag.
+///
+/// Dollar quoted bodies are mostly function bodies, so they are counted as source
+/// along with the comments and strings in them.
+pub struct Postgres;
+impl Language for Postgres {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        LINE_COMMENT,
+        ParseItem::Nested(&BLOCK_COMMENT),
+        ParseItem::Preceded(
+            NOT_AFTER_WORD,
+            &ParseItem::WithInner(
+                &ParseItem::UnEscaped(&ParseItem::InSource(
+                    ItemRange::start_matcher(
+                        Matcher::Exact("$"),
+                        DOLLAR_QUOTE_TAG,
+                        Matcher::Exact("$"),
+                    )
+                    .end_matcher(
+                        Matcher::Exact("$"),
+                        DOLLAR_QUOTE_TAG,
+                        Matcher::Exact("$"),
+                    ),
+                    true,
+                )),
+                DOLLAR_QUOTED_BODY,
+            ),
+        ),
+        // escape strings, eg. `E'it\'s'`
+        ParseItem::Preceded(
+            NOT_AFTER_WORD,
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::start_matcher(
+                    Matcher::Fn(&(|src| src.starts_with(['E', 'e']).then(|| &src[..1]))),
+                    Matcher::Empty,
+                    Matcher::Exact("\'"),
+                )
+                .fixed_end("\'"),
+                false,
+            )),
+        ),
+        STRING,
+        QUOTED_IDENTIFIER,
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = &["(", ")", ";", ","];
+}
+
+/// MySQL, `#` comments and backslash escapes in strings, `--` needs a space after it
+pub struct MySql;
+impl Language for MySql {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::start_matcher(
+                Matcher::Fn(
+                    &(|src| {
+                        let rest = src.strip_prefix("--")?;
+                        (rest.is_empty() || rest.starts_with(char::is_whitespace))
+                            .then(|| &src[..2])
+                    }),
+                ),
+                Matcher::Empty,
+                Matcher::Empty,
+            )
+            .pre_fixed_end("\n"),
+            false,
+        )),
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("#").pre_fixed_end("\n"),
+            false,
+        )),
+        BLOCK_COMMENT,
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\'").fixed_end("\'"),
+            false,
+        )),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\"").fixed_end("\""),
+            false,
+        )),
+        ParseItem::UnEscaped(&ParseItem::InSource(
+            ItemRange::fixed_start("`").fixed_end("`"),
+            false,
+        )),
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = &["(", ")", ";", ","];
+}
+
+/// T-SQL, block comments nest and identifiers can be `[bracketed]`
+pub struct TSql;
+impl Language for TSql {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        LINE_COMMENT,
+        ParseItem::Nested(&BLOCK_COMMENT),
+        STRING,
+        QUOTED_IDENTIFIER,
+        ParseItem::UnEscaped(&ParseItem::InSource(
+            ItemRange::fixed_start("[").fixed_end("]"),
+            false,
+        )),
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = &["(", ")", ";", ","];
+}
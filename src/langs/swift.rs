@@ -0,0 +1,83 @@
+use super::C_LIKE_TRIVIAL_TOKENS;
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
+
+/// `\(expr)` interpolation in strings is source
+const SWIFT_INTERPOLATION: &[ParseItem] = &[ParseItem::SameAsSrcInterpolation(
+    ItemRange::fixed_start("\\(").fixed_end(")"),
+)];
+
+/// `\#(expr)` interpolation in strings with extended delimiters, with as many `#`
+/// as the delimiter, eg. `\##(x)` in `##"..."##`
+const SWIFT_EXTENDED_INTERPOLATION: &[ParseItem] = &[ParseItem::SameAsSrcInterpolation(
+    ItemRange::start_matcher(
+        Matcher::Exact("\\#"),
+        Matcher::Repeat("#"),
+        Matcher::Exact("("),
+    )
+    .fixed_end(")"),
+)];
+
+/// Swift block comments nest, and strings with extended delimiters
+/// like `#"..."#` are keyed on the count of `#`
+pub struct Swift;
+impl Language for Swift {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("//").pre_fixed_end("\n"),
+            false,
+        )),
+        ParseItem::Nested(&ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("/*").fixed_end("*/"),
+            false,
+        ))),
+        ParseItem::WithInner(
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("\"\"\"").fixed_end("\"\"\""),
+                false,
+            )),
+            SWIFT_INTERPOLATION,
+        ),
+        ParseItem::WithInner(
+            &ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("\"").fixed_end("\""),
+                false,
+            )),
+            SWIFT_INTERPOLATION,
+        ),
+        // ##"""..."""##, escapes and interpolation need the `#` as well, eg. `\#(x)`
+        ParseItem::WithInner(
+            &ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::start_matcher(
+                    Matcher::Exact("#"),
+                    Matcher::Repeat("#"),
+                    Matcher::Exact("\"\"\""),
+                )
+                .end_matcher(
+                    Matcher::Exact("\"\"\"#"),
+                    Matcher::Repeat("#"),
+                    Matcher::Empty,
+                ),
+                true,
+            )),
+            SWIFT_EXTENDED_INTERPOLATION,
+        ),
+        // #"..."#
+        ParseItem::WithInner(
+            &ParseItem::UnEscaped(&ParseItem::String(
+                ItemRange::start_matcher(
+                    Matcher::Exact("#"),
+                    Matcher::Repeat("#"),
+                    Matcher::Exact("\""),
+                )
+                .end_matcher(
+                    Matcher::Exact("\"#"),
+                    Matcher::Repeat("#"),
+                    Matcher::Empty,
+                ),
+                true,
+            )),
+            SWIFT_EXTENDED_INTERPOLATION,
+        ),
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = C_LIKE_TRIVIAL_TOKENS;
+}
@@ -0,0 +1,129 @@
+use super::ecmascript::{REGEX, TEMPLATE_LITERAL};
+use super::C_LIKE_TRIVIAL_TOKENS;
+use crate::parse::{ItemRange, Language, Matcher, ParseItem};
+
+/// Text in JSX elements begins right after the `>` of a tag, eg. `<p>don't</p>`,
+/// but not after arrows or comparisons like `x => y` or `a >= b`
+fn is_after_jsx_tag(before: &str) -> bool {
+    if !before.ends_with('>') || before.ends_with("=>") || before.ends_with("->") {
+        return false;
+    }
+    let Some(lt) = before.rfind('<') else {
+        return false;
+    };
+    let tag = &before[lt..];
+    !tag.contains('\n')
+        && (tag.starts_with("</")
+            || tag == "<>"
+            || tag.ends_with("/>")
+            || (tag[1..].starts_with(char::is_alphabetic) && is_jsx_context(&before[..lt])))
+}
+
+/// Opening tags follow an operator, a keyword like `return` or other JSX on the same
+/// line, where generics follow a name, eg. `<b>` in `return <b>` and `<p>a <b>`
+/// but not `<string>` in `Array<string>`
+fn is_jsx_context(before: &str) -> bool {
+    let before = before.trim_end();
+    let rest = before.trim_end_matches(|ch: char| ch.is_alphanumeric() || ch == '_' || ch == '$');
+    let word = &before[rest.len()..];
+    if word.is_empty() {
+        !before.ends_with(['.', ')', ']'])
+    } else if ["return", "yield", "await", "default"].contains(&word) {
+        true
+    } else {
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        before[line_start..]
+            .rfind('>')
+            .is_some_and(|gt| is_after_jsx_tag(&before[..line_start + gt + 1]))
+    }
+}
+
+const JSX_TEXT_PRECEDED_BY: Matcher =
+    Matcher::Fn(&(|before| is_after_jsx_tag(before).then_some("")));
+
+/// JSX text stops at the next tag, expression or the end of the line
+const JSX_TEXT: Matcher = Matcher::Fn(
+    &(|src| {
+        let end = src.find(['<', '{', '\n']).unwrap_or(src.len());
+        (end > 0).then(|| &src[..end])
+    }),
+);
+
+/// Closing tags, so that the `/` of `</p>` doesn't begin a regex literal
+const JSX_CLOSING_TAG: Matcher = Matcher::Fn(
+    &(|src| {
+        let rest = src.strip_prefix("</")?;
+        let rest = rest.trim_start_matches(|ch: char| ch.is_alphanumeric() || "_$.:-".contains(ch));
+        rest.starts_with('>')
+            .then(|| &src[..src.len() - rest.len() + 1])
+    }),
+);
+
+/// TypeScript, template literals nest source within `${}`
+pub struct TypeScript;
+impl Language for TypeScript {
+    const PARSE_ITEMS: &'static [ParseItem] = &[
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("//").pre_fixed_end("\n"),
+            false,
+        )),
+        ParseItem::UnEscaped(&ParseItem::Comment(
+            ItemRange::fixed_start("/*").fixed_end("*/"),
+            false,
+        )),
+        TEMPLATE_LITERAL,
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\"").fixed_end("\""),
+            false,
+        )),
+        ParseItem::Escaped(&ParseItem::String(
+            ItemRange::fixed_start("\'").fixed_end("\'"),
+            false,
+        )),
+        REGEX,
+    ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = C_LIKE_TRIVIAL_TOKENS;
+}
+
+/// TypeScript with JSX, quotes in JSX text don't begin strings
+pub struct Tsx;
+impl Language for Tsx {
+    const PARSE_ITEMS: &'static [ParseItem] =
+        &[
+            ParseItem::UnEscaped(&ParseItem::Comment(
+                ItemRange::fixed_start("//").pre_fixed_end("\n"),
+                false,
+            )),
+            ParseItem::UnEscaped(&ParseItem::Comment(
+                ItemRange::fixed_start("/*").fixed_end("*/"),
+                false,
+            )),
+            ParseItem::Preceded(
+                JSX_TEXT_PRECEDED_BY,
+                &ParseItem::UnEscaped(&ParseItem::InSource(
+                    ItemRange::start_matcher(JSX_TEXT, Matcher::Empty, Matcher::Empty).end_matcher(
+                        Matcher::Empty,
+                        Matcher::Empty,
+                        Matcher::Empty,
+                    ),
+                    false,
+                )),
+            ),
+            ParseItem::UnEscaped(&ParseItem::InSource(
+                ItemRange::start_matcher(JSX_CLOSING_TAG, Matcher::Empty, Matcher::Empty)
+                    .end_matcher(Matcher::Empty, Matcher::Empty, Matcher::Empty),
+                false,
+            )),
+            TEMPLATE_LITERAL,
+            ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("\"").fixed_end("\""),
+                false,
+            )),
+            ParseItem::Escaped(&ParseItem::String(
+                ItemRange::fixed_start("\'").fixed_end("\'"),
+                false,
+            )),
+            REGEX,
+        ];
+    const TRIVIAL_TOKENS: &'static [&'static str] = C_LIKE_TRIVIAL_TOKENS;
+}
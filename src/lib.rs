@@ -4,6 +4,9 @@ pub mod langs;
 // WIP Simplified Rust API for count dracula
 pub mod count;
 
+// Jupyter notebooks, counted cell by cell
+pub mod notebook;
+
 // ALl our tests live here.
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,126 @@
+//! Counting for Jupyter notebooks (`.ipynb`), code cells are parsed in the kernel language
+//! and markdown cells are treated as documentation.
+
+use serde_json::Value;
+
+use crate::count::CountPolicy;
+use crate::langs::*;
+use crate::parse::Embedded;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellKind {
+    Code,
+    /// documentation, never meaningful
+    Markdown,
+    /// passed through as is by nbconvert, never meaningful
+    Raw,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellCount {
+    /// index of the cell in the notebook
+    pub index: usize,
+    pub kind: CellKind,
+    /// count of non blank lines in the cell
+    pub lines: usize,
+    /// meaningful line indices, relative to the start of the cell
+    pub meaningful_lines: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotebookCount {
+    /// the kernel language the code cells were parsed as
+    pub language: &'static str,
+    pub cells: Vec<CellCount>,
+}
+
+impl NotebookCount {
+    /// Count of meaningful lines in the whole notebook
+    pub fn meaningful_lines(&self) -> usize {
+        self.cells.iter().map(|c| c.meaningful_lines.len()).sum()
+    }
+
+    /// Count of non blank lines in markdown cells
+    pub fn documentation_lines(&self) -> usize {
+        self.cells
+            .iter()
+            .filter(|c| c.kind == CellKind::Markdown)
+            .map(|c| c.lines)
+            .sum()
+    }
+}
+
+/// Picks the language for the kernel language named in the notebook metadata
+pub fn get_kernel_language(name: &str) -> Option<Embedded> {
+    match name.to_ascii_lowercase().as_str() {
+        "python" | "python2" | "python3" | "ipython" => Some(Embedded::of::<Python>("python")),
+        "rust" => Some(Embedded::of::<Rust>("rust")),
+        "c" => Some(Embedded::of::<C>("c")),
+        // xeus-cling names the standard, eg. `C++17`
+        "c++" | "cpp" | "c++11" | "c++14" | "c++17" | "c++20" => Some(Embedded::of::<Cpp>("cpp")),
+        "go" => Some(Embedded::of::<Go>("go")),
+        "kotlin" => Some(Embedded::of::<Kotlin>("kotlin")),
+        "typescript" => Some(Embedded::of::<TypeScript>("typescript")),
+        "java" => Some(Embedded::of::<Java>("java")),
+        "scala" => Some(Embedded::of::<Scala>("scala")),
+        "ruby" => Some(Embedded::of::<Ruby>("ruby")),
+        "c#" | "csharp" => Some(Embedded::of::<Csharp>("csharp")),
+        "julia" => Some(Embedded::of::<Julia>("julia")),
+        "r" | "ir" => Some(Embedded::of::<R>("r")),
+        _ => None,
+    }
+}
+
+/// Cell source is either a string or a list of lines
+fn cell_source(cell: &Value) -> String {
+    match cell.get("source") {
+        Some(Value::String(src)) => src.clone(),
+        Some(Value::Array(lines)) => lines.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+/// Parses the notebook json and counts the meaningful lines of each cell, code cells
+/// are parsed in the kernel language from the metadata, defaulting to [`Python`].
+///
+/// Returns `None` if the notebook isn't valid json or is written in an unsupported language.
+pub fn get_meaningful_lines_of_notebook(src: &str, policy: CountPolicy) -> Option<NotebookCount> {
+    let notebook: Value = serde_json::from_str(src).ok()?;
+    let metadata = notebook.get("metadata");
+    let language = metadata
+        .and_then(|m| m.pointer("/kernelspec/language"))
+        .or_else(|| metadata.and_then(|m| m.pointer("/language_info/name")))
+        .and_then(Value::as_str)
+        .unwrap_or("python");
+    let language = get_kernel_language(language)?;
+    let cells = notebook
+        .get("cells")?
+        .as_array()?
+        .iter()
+        .enumerate()
+        .map(|(index, cell)| {
+            let src = cell_source(cell);
+            let lines = src.lines().filter(|l| !l.trim().is_empty()).count();
+            let (kind, meaningful_lines) = match cell.get("cell_type").and_then(Value::as_str) {
+                Some("code") => (CellKind::Code, language.meaningful_lines(&src, policy)),
+                Some("markdown") => (CellKind::Markdown, vec![]),
+                _ => (CellKind::Raw, vec![]),
+            };
+            CellCount {
+                index,
+                kind,
+                lines,
+                meaningful_lines,
+            }
+        })
+        .collect();
+    Some(NotebookCount {
+        language: language.name,
+        cells,
+    })
+}
+
+/// Counts the meaningful lines in the whole notebook, see [`get_meaningful_lines_of_notebook`]
+pub fn get_count_of_meaningful_lines_of_notebook(src: &str, policy: CountPolicy) -> Option<usize> {
+    get_meaningful_lines_of_notebook(src, policy).map(|n| n.meaningful_lines())
+}
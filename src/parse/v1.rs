@@ -3,6 +3,7 @@ use core::fmt::Debug;
 #[cfg(backtrace)]
 use std::backtrace::Backtrace;
 use std::marker::PhantomData;
+use std::ops::Range;
 
 #[derive(Clone, Copy, Debug)]
 pub struct Span {
@@ -46,6 +47,9 @@ impl Matcher {
                     // only pre-exact is newline,
                     // and we know what the src has ended!
                     Some(&src[..s.len()])
+                } else if s.eq(&"\n") && src.is_empty() {
+                    // source ended without a trailing newline
+                    Some(src)
                 } else {
                     None
                 }
@@ -98,10 +102,18 @@ impl EndPoint {
         let s3 = s2 + end_match.len();
         Some([Span::new(0, s1), Span::new(s1, s2), Span::new(s2, s3)])
     }
+    /// Length of the part of the beginning that nested items nest on at the start of
+    /// `src`, its end if it has one, eg. `(` of `%w(`, or else the whole of it
+    pub fn opening_len(&self, src: &str) -> Option<usize> {
+        match self.end {
+            Matcher::Empty => self.matches(src).map(|matches| matches[2].end),
+            _ => self.end.get_match(src).map(str::len),
+        }
+    }
     pub fn matches_with_key(&self, src: &str, key: &str) -> Option<Matches> {
         self.matches(src).and_then(|span| {
             let Span { start, end } = span[1];
-            src[start..end].starts_with(key).then_some(span)
+            (&src[start..end] == key).then_some(span)
         })
     }
 }
@@ -122,15 +134,28 @@ pub enum ParseItem {
     Comment(ItemRange, bool),
     String(ItemRange, bool),
     // WithInner is for ParseItem that contain something else inside them,
-    // for which we need a separate parse.
-    WithInner(&'static ParseItem, &'static ParseItem),
-    // SameAsSrcInterpolation is added just for Ruby for now
+    // for which we need a separate parse, eg. templates in strings.
+    // Keyed items end at their key even where an inner item could begin, and
+    // their inner items with a key only begin with the same key.
+    WithInner(&'static ParseItem, &'static [ParseItem]),
+    // SameAsSrcInterpolation is source inside of a WithInner item, eg. `${x}`,
+    // it nests the bracket it's opened with
     SameAsSrcInterpolation(ItemRange),
     // Represents things like format strings, or the general case of
     // embedded DSLs which interpolate meaningful source within themselves.
     InSource(ItemRange, bool),
+    // Preprocessor directives, eg. `#include` or `#if` in C
+    Preprocessor(ItemRange, bool),
     Escaped(&'static ParseItem),
     UnEscaped(&'static ParseItem),
+    // Only allows the item to begin at the start of a line
+    LineStart(&'static ParseItem),
+    // The item can contain itself, eg. nested block comments, items that begin
+    // with a delimiter nest on just the delimiter, eg. `(` of `%w(` in Ruby
+    Nested(&'static ParseItem),
+    // Only allows the item to begin when the matcher matches the source
+    // before it, eg. regex literals after operators
+    Preceded(Matcher, &'static ParseItem),
 }
 
 #[derive(Debug)]
@@ -197,8 +222,14 @@ impl ParseItem {
             Self::String(s, _)
             | Self::Comment(s, _)
             | Self::InSource(s, _)
+            | Self::Preprocessor(s, _)
             | Self::SameAsSrcInterpolation(s) => &s.begin,
-            Self::Escaped(item) | Self::UnEscaped(item) | Self::WithInner(item, _) => item.begin(),
+            Self::Escaped(item)
+            | Self::UnEscaped(item)
+            | Self::LineStart(item)
+            | Self::Nested(item)
+            | Self::Preceded(_, item)
+            | Self::WithInner(item, _) => item.begin(),
         }
     }
     pub fn end(&self) -> &EndPoint {
@@ -206,14 +237,28 @@ impl ParseItem {
             Self::String(s, _)
             | Self::Comment(s, _)
             | Self::InSource(s, _)
+            | Self::Preprocessor(s, _)
             | Self::SameAsSrcInterpolation(s) => &s.end,
-            Self::Escaped(item) | Self::UnEscaped(item) | Self::WithInner(item, _) => item.end(),
+            Self::Escaped(item)
+            | Self::UnEscaped(item)
+            | Self::LineStart(item)
+            | Self::Nested(item)
+            | Self::Preceded(_, item)
+            | Self::WithInner(item, _) => item.end(),
         }
     }
     pub fn is_keyed(&self) -> bool {
         match self {
-            Self::Escaped(k) | Self::UnEscaped(k) => k.is_keyed(),
-            Self::String(_, true) | Self::Comment(_, true) => true,
+            Self::Escaped(k)
+            | Self::UnEscaped(k)
+            | Self::LineStart(k)
+            | Self::Nested(k)
+            | Self::Preceded(_, k)
+            | Self::WithInner(k, _) => k.is_keyed(),
+            Self::String(_, true)
+            | Self::Comment(_, true)
+            | Self::InSource(_, true)
+            | Self::Preprocessor(_, true) => true,
             _ => false,
         }
     }
@@ -221,14 +266,74 @@ impl ParseItem {
         match self {
             Self::Comment(..) => ParseOutput::Comment(src),
             Self::String(..) => ParseOutput::String(src),
-            Self::Escaped(pi) | Self::UnEscaped(pi) | Self::WithInner(pi, _) => {
-                pi.to_parse_output(src)
-            }
+            Self::Preprocessor(..) => ParseOutput::Preprocessor(src),
+            Self::Escaped(pi)
+            | Self::UnEscaped(pi)
+            | Self::LineStart(pi)
+            | Self::Nested(pi)
+            | Self::Preceded(_, pi)
+            | Self::WithInner(pi, _) => pi.to_parse_output(src),
             _ => ParseOutput::Source(src),
         }
     }
     pub fn is_escaped(&self) -> bool {
-        matches!(self, ParseItem::Escaped(_))
+        match self {
+            Self::Escaped(_) => true,
+            Self::LineStart(pi)
+            | Self::Nested(pi)
+            | Self::Preceded(_, pi)
+            | Self::WithInner(pi, _) => pi.is_escaped(),
+            _ => false,
+        }
+    }
+    /// Checks if the item can begin after the source `before` it,
+    /// see [`ParseItem::LineStart`] and [`ParseItem::Preceded`]
+    pub fn can_begin_after(&self, before: &str) -> bool {
+        match self {
+            Self::LineStart(pi) => {
+                (before.is_empty() || before.ends_with('\n')) && pi.can_begin_after(before)
+            }
+            Self::Preceded(matcher, pi) => {
+                matcher.get_match(before).is_some() && pi.can_begin_after(before)
+            }
+            Self::Escaped(pi) | Self::UnEscaped(pi) | Self::Nested(pi) | Self::WithInner(pi, _) => {
+                pi.can_begin_after(before)
+            }
+            _ => true,
+        }
+    }
+    pub fn is_nested(&self) -> bool {
+        match self {
+            Self::Nested(_) => true,
+            Self::Escaped(pi)
+            | Self::UnEscaped(pi)
+            | Self::LineStart(pi)
+            | Self::Preceded(_, pi)
+            | Self::WithInner(pi, _) => pi.is_nested(),
+            _ => false,
+        }
+    }
+    /// Checks if the body of the item is source with inner items in it
+    pub fn is_source(&self) -> bool {
+        match self {
+            Self::WithInner(pi, _) => matches!(pi.to_parse_output(""), ParseOutput::Source(_)),
+            Self::Escaped(pi)
+            | Self::UnEscaped(pi)
+            | Self::LineStart(pi)
+            | Self::Preceded(_, pi) => pi.is_source(),
+            _ => false,
+        }
+    }
+    /// Items that can be inside of this item, see [`ParseItem::WithInner`]
+    pub fn inner_items(&self) -> &'static [ParseItem] {
+        match self {
+            Self::WithInner(_, inner) => inner,
+            Self::Escaped(pi)
+            | Self::UnEscaped(pi)
+            | Self::LineStart(pi)
+            | Self::Preceded(_, pi) => pi.inner_items(),
+            _ => &[],
+        }
     }
 }
 
@@ -237,6 +342,7 @@ pub enum ParseOutput<'a> {
     Comment(&'a str),
     String(&'a str),
     Source(&'a str),
+    Preprocessor(&'a str),
     Invalid(usize, usize),
     EOL(&'a str),
     EOF,
@@ -246,22 +352,54 @@ impl ParseOutput<'_> {
     pub fn is_meaningful<L: Language>(&self) -> bool {
         match self {
             Self::Source(src) => L::is_meaningful_src(src),
+            Self::Preprocessor(_) => true,
             _ => false,
         }
     }
     pub fn len(&self) -> usize {
         match self {
-            Self::Comment(s) | Self::String(s) | Self::Source(s) => s.len(),
+            Self::Comment(s) | Self::String(s) | Self::Source(s) | Self::Preprocessor(s) => s.len(),
             Self::EOL(_) => 1,
             Self::Invalid(..) | Self::EOF => 0,
         }
     }
 }
 
+/// Checks if `src` is made up of only the given trivial tokens, words are matched as
+/// a whole (`end` doesn't match `endless`) and everything else as punctuation
+pub fn is_trivial_src(mut src: &str, trivial_tokens: &[&str]) -> bool {
+    let is_word_char = |ch: char| ch.is_alphanumeric() || ch == '_';
+    loop {
+        src = src.trim_start();
+        let Some(ch) = src.chars().next() else {
+            return true;
+        };
+        let token_len = if is_word_char(ch) {
+            let len = src.find(|ch| !is_word_char(ch)).unwrap_or(src.len());
+            if !trivial_tokens.contains(&&src[..len]) {
+                return false;
+            }
+            len
+        } else if let Some(token) = trivial_tokens
+            .iter()
+            .filter(|token| !token.starts_with(is_word_char) && src.starts_with(*token))
+            .max_by_key(|token| token.len())
+        {
+            token.len()
+        } else {
+            return false;
+        };
+        src = &src[token_len..];
+    }
+}
+
 pub trait Language: Sized {
     const PARSE_ITEMS: &'static [ParseItem];
+    /// Tokens and keywords which don't mean anything on their own,
+    /// source made up of only these (eg. `});` or `end`) isn't meaningful
+    const TRIVIAL_TOKENS: &'static [&'static str] = &[];
     fn is_meaningful_src(src: &str) -> bool {
-        src.chars().next().is_some() && !src.chars().all(char::is_whitespace)
+        !is_trivial_src(src, Self::TRIVIAL_TOKENS)
     }
     fn get_parser(src: &str) -> Parser<Self> {
         Parser::<Self>::new(src)
@@ -269,6 +407,134 @@ pub trait Language: Sized {
     fn is_meaningful(parse_output: &ParseOutput) -> bool {
         ParseOutput::is_meaningful::<Self>(parse_output)
     }
+    /// Byte ranges of the source that are never compiled, eg. `#if 0` blocks in C,
+    /// lines that lie completely inside them are not meaningful.
+    fn inactive_spans(_src: &str) -> Vec<std::ops::Range<usize>> {
+        vec![]
+    }
+}
+
+/// A [`Language`] embedded inside of a [`Composite`] source,
+/// eg. JavaScript inside of `<script>` in HTML
+#[derive(Debug, Clone, Copy)]
+pub struct Embedded {
+    /// name of the language, regions with the same name are counted together
+    pub name: &'static str,
+    meaningful_lines: fn(&str, crate::count::CountPolicy) -> Vec<usize>,
+}
+
+impl Embedded {
+    pub const fn of<L: Language + 'static>(name: &'static str) -> Self {
+        Self {
+            name,
+            meaningful_lines: |src, policy| {
+                crate::count::get_meaningful_line_indices::<L>(src)
+                    .with_policy(policy)
+                    .flatten()
+                    .collect()
+            },
+        }
+    }
+
+    /// Meaningful line indices of `src` when parsed as this language
+    pub fn meaningful_lines(&self, src: &str, policy: crate::count::CountPolicy) -> Vec<usize> {
+        (self.meaningful_lines)(src, policy)
+    }
+}
+
+/// Sources that switch between languages based on the tags in them,
+/// eg. HTML with `<script>` and `<style>`, or Vue and Svelte components
+pub trait Composite: Sized {
+    /// The language outside of the embedded blocks
+    const HOST: Embedded;
+    /// Tags whose content is written in another language
+    const EMBEDDING_TAGS: &'static [&'static str] = &["script", "style"];
+    /// Picks the language of the content of `<tag attributes>`,
+    /// `None` leaves the content to the host language
+    fn embedded(tag: &str, attributes: &str) -> Option<Embedded>;
+    /// Splits the source into the embedded regions, defaults to [`get_embedded_regions`]
+    fn embedded_regions(src: &str) -> Vec<(Embedded, std::ops::Range<usize>)> {
+        get_embedded_regions::<Self>(src)
+    }
+}
+
+/// Finds the value of `name` in the attributes of a tag, eg. `ts` for `lang` in `lang="ts"`
+pub fn get_attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attributes;
+    while let Some(i) = rest.find(name) {
+        let is_start = rest[..i].ends_with(char::is_whitespace) || i == 0;
+        let after = rest[i + name.len()..].trim_start();
+        rest = &rest[i + name.len()..];
+        if !is_start || !after.starts_with('=') {
+            continue;
+        }
+        let value = after[1..].trim_start();
+        return Some(match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+            _ => value
+                .split(|ch: char| ch.is_whitespace() || ch == '>')
+                .next()
+                .unwrap_or_default(),
+        });
+    }
+    None
+}
+
+/// Splits a [`Composite`] source into the spans of content of its embedded blocks,
+/// along with the language they are written in.
+pub fn get_embedded_regions<C: Composite>(src: &str) -> Vec<(Embedded, std::ops::Range<usize>)> {
+    let lower = src.to_ascii_lowercase();
+    let mut regions = vec![];
+    let mut index = 0;
+    while let Some(i) = lower[index..].find('<').map(|i| i + index) {
+        index = i + 1;
+        if lower[i..].starts_with("<!--") {
+            index = lower[i..].find("-->").map_or(src.len(), |e| i + e + 3);
+            continue;
+        }
+        let Some(tag) = C::EMBEDDING_TAGS.iter().find(|tag| {
+            lower[i + 1..].starts_with(*tag)
+                && lower[i + 1 + tag.len()..]
+                    .starts_with(|ch: char| ch.is_whitespace() || ch == '>')
+        }) else {
+            continue;
+        };
+        // find the end of the opening tag, skipping over quoted attributes
+        let attributes_start = i + 1 + tag.len();
+        let mut quote = None;
+        let Some(content_start) = src[attributes_start..]
+            .char_indices()
+            .find(|&(_, ch)| match quote {
+                Some(q) if ch == q => {
+                    quote = None;
+                    false
+                }
+                Some(_) => false,
+                None if ch == '"' || ch == '\'' => {
+                    quote = Some(ch);
+                    false
+                }
+                None => ch == '>',
+            })
+            .map(|(e, _)| attributes_start + e + 1)
+        else {
+            break;
+        };
+        let attributes = &src[attributes_start..content_start - 1];
+        if attributes.trim_end().ends_with('/') {
+            // self closing tags have no content
+            index = content_start;
+            continue;
+        }
+        let content_end = lower[content_start..]
+            .find(&format!("</{tag}"))
+            .map_or(src.len(), |e| content_start + e);
+        if let Some(embedded) = C::embedded(tag, attributes) {
+            regions.push((embedded, content_start..content_end));
+        }
+        index = content_end;
+    }
+    regions
 }
 
 #[derive(Debug, Default)]
@@ -276,9 +542,35 @@ pub struct Parser<'a, L: Language> {
     src: &'a str,
     index: usize,
     language_items: &'static [ParseItem],
+    /// the item whose body continues after an inner item, see [`ParseItem::WithInner`],
+    /// along with the span of its key in the source and the depth it's nested to
+    resume: Option<(&'static ParseItem, Range<usize>, usize)>,
+    /// inner items being parsed as source, innermost last
+    inner_stack: Vec<InnerFrame>,
     _marker: PhantomData<L>,
 }
 
+/// An inner item of a [`ParseItem::WithInner`] being parsed as source, eg. `${ x }`
+#[derive(Debug)]
+struct InnerFrame {
+    outer: &'static ParseItem,
+    outer_key: Range<usize>,
+    outer_depth: usize,
+    inner: &'static ParseItem,
+    /// the bracket the inner item was opened with, and its closing bracket
+    brackets: Option<(char, char)>,
+    depth: usize,
+}
+
+/// Where the scan over the body of an item stopped
+enum BodyEnd {
+    /// the item ended, with the match of its end at the offset
+    End(usize, Matches),
+    /// one of the inner items begins at the offset, with the depth the item
+    /// is nested to there
+    Inner(usize, usize),
+}
+
 // most this is only used in tests atm!
 impl<L: Language> Parser<'_, L> {
     /// Creates a new [`Parser`].
@@ -287,55 +579,227 @@ impl<L: Language> Parser<'_, L> {
             src,
             language_items: L::PARSE_ITEMS,
             index: 0,
+            resume: None,
+            inner_stack: vec![],
             _marker: PhantomData::default(),
         }
     }
 
+    /// Matches the beginning of `item` at `src[at..]`, where `src` is the rest of the
+    /// source from the current index.
+    fn begin_matches(&self, item: &ParseItem, src: &str, at: usize) -> Option<Matches> {
+        if !item.can_begin_after(&self.src[..self.index + at]) {
+            return None;
+        }
+        item.begin().matches(&src[at..])
+    }
+
+    /// Matches the beginning of an `inner` item of `item` at `src[at..]`, inner items
+    /// with a key only begin with the `key` of the keyed item they're in,
+    /// eg. `\#(` in `#"..."#` in Swift
+    fn inner_begin_matches(
+        &self,
+        item: &ParseItem,
+        inner: &ParseItem,
+        src: &str,
+        at: usize,
+        key: &str,
+    ) -> Option<Matches> {
+        let matches = self.begin_matches(inner, src, at)?;
+        let Span { start, end } = matches[1];
+        (!item.is_keyed()
+            || matches!(inner.begin().key, Matcher::Empty)
+            || &src[at + start..at + end] == key)
+            .then_some(matches)
+    }
+
+    /// Scans the body of `item` starting at `from` for its end, `key` is the key
+    /// matched by the beginning of keyed items and `depth` is how deep nested
+    /// items are nested at `from`
+    fn find_body_end(
+        &self,
+        item: &ParseItem,
+        src: &str,
+        from: usize,
+        key: &str,
+        mut depth: usize,
+    ) -> Option<BodyEnd> {
+        let mut escape = false;
+        let mut skip_till = from;
+        (from..=src.len()).find_map(|b| {
+            if b < skip_till {
+                None
+            } else if src.is_char_boundary(b) && !escape {
+                // the key ends an item before any of its inner items can begin,
+                // eg. `$fn$` isn't an inner `$tag$` quote in Postgres
+                if item.is_keyed() && depth == 0 {
+                    if let Some(matches) = item.end().matches_with_key(&src[b..], key) {
+                        return Some(BodyEnd::End(b, matches));
+                    }
+                }
+                // source bodies are split after each line, so lines with only
+                // inner comments in them aren't meaningful
+                if b > from && item.is_source() && src[..b].ends_with('\n') {
+                    return Some(BodyEnd::Inner(b, depth));
+                }
+                // inner items can begin with an escape, eg. `\(x)` in Swift
+                if item
+                    .inner_items()
+                    .iter()
+                    .any(|inner| self.inner_begin_matches(item, inner, src, b, key).is_some())
+                {
+                    return Some(BodyEnd::Inner(b, depth));
+                }
+                if item.is_escaped() && src[b..].starts_with('\\') {
+                    escape = true;
+                    return None;
+                }
+                if item.is_nested() {
+                    if let Some(len) = item.begin().opening_len(&src[b..]) {
+                        depth += 1;
+                        skip_till = b + len;
+                        return None;
+                    }
+                }
+                let matches = if item.is_keyed() {
+                    item.end().matches_with_key(&src[b..], key)?
+                } else {
+                    item.end().matches(&src[b..])?
+                };
+                if depth > 0 {
+                    depth -= 1;
+                    skip_till = b + matches[2].end;
+                    return None;
+                }
+                Some(BodyEnd::End(b, matches))
+            } else {
+                if escape {
+                    escape = false;
+                }
+                None
+            }
+        })
+    }
+
+    /// Parses the rest of the body of `item` after one of its inner items,
+    /// or begins the next inner item
+    fn parse_resumed<'a>(
+        &mut self,
+        item: &'static ParseItem,
+        key: Range<usize>,
+        depth: usize,
+        src: &'a str,
+    ) -> ParseOutput<'a> {
+        let whole_src = self.src;
+        let outer_key = &whole_src[key.clone()];
+        if let Some((inner, matches)) = item.inner_items().iter().find_map(|inner| {
+            Some((
+                inner,
+                self.inner_begin_matches(item, inner, src, 0, outer_key)?,
+            ))
+        }) {
+            let begin = &src[..matches[2].end];
+            // inner items without a body, eg. `$name`
+            if let Some(end_matches) = inner.end().matches(&src[begin.len()..]) {
+                self.resume = Some((item, key, depth));
+                return inner.to_parse_output(&src[..begin.len() + end_matches[2].end]);
+            }
+            self.inner_stack.push(InnerFrame {
+                outer: item,
+                outer_key: key,
+                outer_depth: depth,
+                inner,
+                brackets: match begin.chars().last() {
+                    Some('{') => Some(('{', '}')),
+                    Some('(') => Some(('(', ')')),
+                    Some('[') => Some(('[', ']')),
+                    _ => None,
+                },
+                depth: 0,
+            });
+            return inner.to_parse_output(begin);
+        }
+        match self.find_body_end(item, src, 0, outer_key, depth) {
+            Some(BodyEnd::End(b, end_matches)) => {
+                item.to_parse_output(&src[..b + end_matches[2].end])
+            }
+            Some(BodyEnd::Inner(b, depth)) => {
+                self.resume = Some((item, key, depth));
+                item.to_parse_output(&src[..b])
+            }
+            // unterminated, the rest of the source is a part of the item
+            None => item.to_parse_output(src),
+        }
+    }
+
+    /// Parses the brackets and the end of the innermost inner item
+    fn parse_inner_delimiter<'a>(&mut self, src: &'a str) -> Option<ParseOutput<'a>> {
+        let frame = self.inner_stack.last_mut()?;
+        if frame.depth == 0 {
+            if let Some(end_matches) = frame.inner.end().matches(src) {
+                let output = frame.inner.to_parse_output(&src[..end_matches[2].end]);
+                self.resume = Some((frame.outer, frame.outer_key.clone(), frame.outer_depth));
+                self.inner_stack.pop();
+                return Some(output);
+            }
+        }
+        let (open, close) = frame.brackets?;
+        if src.starts_with(open) {
+            frame.depth += 1;
+        } else if src.starts_with(close) {
+            frame.depth -= 1;
+        } else {
+            return None;
+        }
+        Some(ParseOutput::Source(&src[..1]))
+    }
+
+    /// Checks if `src` begins with a bracket or the end of the innermost inner item
+    fn is_inner_delimiter(&self, src: &str) -> bool {
+        self.inner_stack.last().is_some_and(|frame| {
+            (frame.depth == 0 && frame.inner.end().matches(src).is_some())
+                || frame
+                    .brackets
+                    .is_some_and(|(open, close)| src.starts_with([open, close]))
+        })
+    }
+
     /// Try to parse as per the given grammar.
     /// This function will return an error if parsing as the given grammar fails
-    fn parse_next<'a>(&self, src: &'a str) -> Result<ParseOutput<'a>, String> {
+    fn parse_next<'a>(&mut self, src: &'a str) -> Result<ParseOutput<'a>, String> {
         let items = self.language_items;
-        if src.starts_with('\n') {
+        if let Some((item, key, depth)) = self.resume.take() {
+            Ok(self.parse_resumed(item, key, depth, src))
+        } else if let Some(output) = self.parse_inner_delimiter(src) {
+            Ok(output)
+        } else if src.starts_with('\n') {
             Ok(ParseOutput::EOL(&src[..1]))
-        } else if let Some((i, b, end_matches)) = (0..items.len())
-            .find_map(|i| Some((i, items[i].begin().matches(src)?)))
-            .and_then(|(i, matches)| {
-                let mut escape = false;
-                (matches[2].end..src.len()).find_map(|b| {
-                    if src.is_char_boundary(b) && !escape {
-                        if items[i].is_escaped() && src[b..].starts_with('\\') {
-                            escape = true;
-                            return None;
-                        }
-                        Some((
-                            i,
-                            b,
-                            if items[i].is_keyed() {
-                                items[i].end().matches_with_key(
-                                    &src[b..],
-                                    &src[matches[1].start..matches[1].end],
-                                )?
-                            } else {
-                                items[i].end().matches(&src[b..])?
-                            },
-                        ))
-                    } else {
-                        if escape {
-                            escape = false;
-                        }
-                        None
-                    }
-                })
+        } else if let Some((item, key, body_end)) = items
+            .iter()
+            .find_map(|item| Some((item, self.begin_matches(item, src, 0)?)))
+            .and_then(|(item, matches)| {
+                let key = &src[matches[1].start..matches[1].end];
+                let body_end = self.find_body_end(item, src, matches[2].end, key, 0)?;
+                Some((item, matches[1].start..matches[1].end, body_end))
             })
         {
-            Ok(items[i].to_parse_output(&src[..b + end_matches[2].end]))
+            Ok(match body_end {
+                BodyEnd::End(b, end_matches) => {
+                    item.to_parse_output(&src[..b + end_matches[2].end])
+                }
+                BodyEnd::Inner(b, depth) => {
+                    self.resume = Some((item, self.index + key.start..self.index + key.end, depth));
+                    item.to_parse_output(&src[..b])
+                }
+            })
         } else if let Some(end) = (1..=src.len()).find(|&idx| {
             src.is_char_boundary(idx)
                 && (idx == src.len()
                     || src[idx..].starts_with('\n')
+                    || self.is_inner_delimiter(&src[idx..])
                     || items
                         .iter()
-                        .find_map(|i| i.begin().matches(&src[idx..]))
+                        .find_map(|i| self.begin_matches(i, src, idx))
                         .is_some())
         }) {
             // if it's not a range then it's a source line
@@ -16,6 +16,8 @@ pub enum TreeSitterLanguage {
     Ruby,
     Python,
     Go,
+    Swift,
+    Dart,
 }
 
 pub struct Parser {
@@ -167,6 +169,8 @@ impl Parser {
             TreeSitterLanguage::Typescript => tree_sitter_typescript::language_typescript(),
             TreeSitterLanguage::TSX => tree_sitter_typescript::language_tsx(),
             TreeSitterLanguage::Kotlin => tree_sitter_kotlin::language(),
+            TreeSitterLanguage::Swift => tree_sitter_swift::language(),
+            TreeSitterLanguage::Dart => tree_sitter_dart::language(),
         };
         parser.set_language(tlang).ok()?;
         Some(Parser { lang, parser })
@@ -195,6 +199,17 @@ impl Parser {
                     false
                 };
             }
+            // kinds of values we only mark as non executable in the grammar they're
+            // from, others like Kotlin have kinds by the same names
+            let lang_kinds: &[&str] = match self.lang {
+                TreeSitterLanguage::Swift => &[
+                    "multiline_comment",
+                    "line_string_literal",
+                    "multi_line_string_literal",
+                ],
+                TreeSitterLanguage::Dart => &["formal_parameter_list", "documentation_comment"],
+                _ => &[],
+            };
             // kinds of values we mark as non executable
             if [
                 "comment",
@@ -210,6 +225,7 @@ impl Parser {
                 ")",
             ]
             .contains(&x.kind())
+                || lang_kinds.contains(&x.kind())
             {
                 non_executable_src_spans.push(x.byte_range());
                 false
@@ -35,9 +35,240 @@ mod simple_c {
             // crate::parse::v2::display_lines(src, &lines);
             lines.len()
         };
-        assert_eq!(cnt, 10);
+        // `) {` and the `C!)";` string continuation are made of trivial tokens
+        assert_eq!(cnt, 8);
         assert_eq!(cnt_executable, 9); // we can now ignore parens and curlies
     }
+
+    #[test]
+    fn preprocessor_directives() {
+        let src = r#"#include <stdio.h>
+            #define MAX(a, b) \
+                ((a) > (b) ? (a) : (b))
+            #if 0
+            int unused() { return "it's dead"; }
+            #endif
+            #if defined(DEBUG) && DEBUG > 1
+            int verbose = 1;
+            #elif 1
+            int verbose = 0;
+            #else
+            int verbose = -1;
+            #endif
+            int main() {}
+        "#;
+        assert_eq!(
+            count::get_meaningful_line_indices::<C>(src)
+                .flatten()
+                .collect::<Vec<_>>(),
+            [0, 1, 2, 3, 5, 6, 7, 8, 9, 10, 12, 13]
+        );
+        assert!(crate::parse::Parser::<C>::new(src).any(|po| matches!(
+            po,
+            crate::parse::ParseOutput::Preprocessor("#include <stdio.h>")
+        )));
+        let cleaned = count::get_cleaned_source_code::<C>(src).unwrap();
+        assert!(!cleaned.contains("unused"));
+        // `#elif 1` is always taken when `#if` isn't
+        assert!(cleaned.contains("int verbose = 0;"));
+        assert!(!cleaned.contains("int verbose = -1;"));
+    }
+
+    #[test]
+    fn preprocessor_with_defines() {
+        let src = r#"
+            #ifdef DEBUG
+            log();
+            #ifndef QUIET
+            print();
+            #endif
+            #elif PLATFORM == 2
+            run();
+            #else
+            fallback();
+            #endif
+            #undef PLATFORM
+            #if PLATFORM
+            never();
+            #endif
+        "#;
+        let lines = |pp: Preprocessor| {
+            count::get_meaningful_line_indices::<C>(src)
+                .with_inactive_spans(pp.inactive_spans::<C>(src))
+                .flatten()
+                .collect::<Vec<_>>()
+        };
+        // nothing is known so only `#if PLATFORM` after `#undef` is decided
+        assert_eq!(
+            lines(Preprocessor::new()),
+            [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 14]
+        );
+        assert_eq!(
+            lines(Preprocessor::new().define("DEBUG", "")),
+            [1, 2, 3, 4, 5, 6, 8, 10, 11, 12, 14]
+        );
+        assert_eq!(
+            lines(Preprocessor::with_defines([("DEBUG", ""), ("QUIET", "")])),
+            [1, 2, 3, 5, 6, 8, 10, 11, 12, 14]
+        );
+        assert_eq!(
+            lines(Preprocessor::new().define("PLATFORM", "1 + 1")),
+            [1, 6, 7, 8, 10, 11, 12, 14]
+        );
+        assert_eq!(
+            lines(Preprocessor::with_defines::<_, &str, &str>([])),
+            [1, 6, 8, 9, 10, 11, 12, 14]
+        );
+    }
+}
+
+#[cfg(test)]
+mod simple_cpp {
+    use crate::count;
+    use crate::langs::*;
+    use crate::parse::v2::get_lines_without_ranges;
+    use crate::parse::v2::Parser;
+    use crate::parse::v2::TreeSitterLanguage;
+    use crate::parse::{ItemRange, Matcher};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn try_parse() {
+        let src = r#"
+        #include <string>
+        // raw strings with punctuation in their delimiters
+        auto json = R"--(
+            {"a": ")"}
+        )--";
+        auto wide = LR"(
+            // not a comment
+        )";
+        auto utf8 = u8R"x*(")x*";
+        long million = 1'000'000; // not a char
+        char quote = '"', wquote = L'\'';
+        int main() { return 0; }
+        "#;
+        let lines = count::get_meaningful_line_indices::<Cpp>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [1, 3, 6, 9, 10, 11, 12]);
+        let cnt_executable = {
+            let mut parser = Parser::new(TreeSitterLanguage::Cpp).unwrap();
+            let ranges = parser.non_executable_src_spans(src).unwrap();
+            let lines = get_lines_without_ranges(src, ranges);
+            // crate::parse::v2::display_lines(src, &lines);
+            lines.len()
+        };
+        // v2 also counts the `;` after the raw strings
+        assert_eq!(cnt_executable, 9);
+    }
+
+    #[test]
+    fn raw_string_keys() {
+        // `)x"` doesn't end a raw string without a delimiter
+        let src = "auto s = R\"(a)x\"\n)\";\nint x;\n";
+        assert_eq!(
+            count::get_cleaned_source_code::<Cpp>(src).unwrap(),
+            "auto s = ;\nint x;\n"
+        );
+    }
+
+    #[test]
+    fn keyed_ends_match_the_whole_key() {
+        let end = ItemRange::fixed_start("R\"")
+            .end_matcher(
+                Matcher::Exact(")"),
+                Matcher::AnyAlphaNumeric,
+                Matcher::Exact("\""),
+            )
+            .end;
+        assert!(end.matches_with_key(")ab\"", "ab").is_some());
+        assert!(end.matches_with_key(")abc\"", "ab").is_none());
+        assert!(end.matches_with_key(")a\"", "ab").is_none());
+        // `)abc"` is a part of the raw string keyed by `ab`
+        let src = "auto s = R\"ab(x)abc\"\n)ab\";\nint x;\n";
+        assert_eq!(
+            count::get_cleaned_source_code::<Cpp>(src).unwrap(),
+            "auto s = ;\nint x;\n"
+        );
+    }
+}
+
+#[cfg(test)]
+mod trivial_lines {
+    use crate::count::get_meaningful_line_indices;
+    use crate::langs::*;
+    use crate::parse::{is_trivial_src, Language};
+
+    fn lines<L: Language + 'static>(src: &str) -> Vec<usize> {
+        get_meaningful_line_indices::<L>(src).flatten().collect()
+    }
+
+    #[test]
+    fn trivial_tokens() {
+        assert!(is_trivial_src(" }); ", &["}", ")", ";"]));
+        assert!(is_trivial_src("end end", &["end"]));
+        assert!(!is_trivial_src("endless", &["end"]));
+        assert!(!is_trivial_src("end.foo", &["end"]));
+        assert!(is_trivial_src(" \t", &[]));
+    }
+
+    #[test]
+    fn trivial_lines_across_languages() {
+        let src = "foo(() => {\n    bar();\n});\nlet x = [\n    1,\n],\n} else {\n";
+        assert_eq!(lines::<C>(src), [0, 1, 3, 4, 6]);
+        assert_eq!(lines::<Java>(src), [0, 1, 3, 4, 6]);
+        // Rust keeps `;` and `,` meaningful, like its AST does
+        assert_eq!(lines::<Rust>(src), [0, 1, 2, 3, 4, 5, 6]);
+        let src = "def foo\n  [1, 2].each do |x|\n    x\n  end\nend # done\nendless\n";
+        assert_eq!(lines::<Ruby>(src), [0, 1, 2, 5]);
+        let src = "foo(\n    1,\n    [2],\n)\n";
+        assert_eq!(lines::<Python>(src), [0, 1, 2]);
+    }
+}
+
+#[cfg(test)]
+mod count_policies {
+    use crate::count::*;
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn presets() {
+        let src = r#"#include <stdio.h>
+            int main() {
+                // comment
+                char* s = "long \
+                    string";
+
+                return 0;
+            }
+        "#;
+        let lines = |policy| {
+            get_meaningful_line_indices::<C>(src)
+                .with_policy(policy)
+                .flatten()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(lines(CountPolicy::default()), [0, 1, 3, 6]);
+        assert_eq!(lines(CountPolicy::PHYSICAL_SLOC), [0, 1, 3, 4, 6, 7]);
+        assert_eq!(lines(CountPolicy::EXECUTABLE), [1, 3, 6]);
+        assert_eq!(lines(CountPolicy::REVIEW), [0, 1, 3, 4, 6]);
+        assert_eq!(
+            get_count_of_meaningful_lines_with_policy::<C>(src, CountPolicy::REVIEW),
+            5
+        );
+    }
+
+    #[test]
+    fn cleaned_source() {
+        let src = "x = [\n    \"a\",\n]\n";
+        assert_eq!(get_cleaned_source_code::<Python>(src).unwrap(), "x = [\n");
+        assert_eq!(
+            get_cleaned_source_code_with_policy::<Python>(src, CountPolicy::PHYSICAL_SLOC).unwrap(),
+            src
+        );
+    }
 }
 
 #[cfg(test)]
@@ -189,6 +420,37 @@ mod simple_python {
         );
     }
 
+    #[test]
+    fn string_prefixes() {
+        let src = r##"x = R"\" # not a comment" + Rb'#' + BR"#" + U"#" + bR'#'
+y = F"{x}" + Rf'#{y}' + fR"{z}#"
+z = xb"a" + ub"b" # invalid prefixes aren't strings
+"##;
+        assert_eq!(
+            get_cleaned_source_code::<Python>(src).unwrap(),
+            "x =  +  +  +  + \ny = F\"{x}\" + Rf'#{y}' + fR\"{z}#\"\nz = xb\"a\" + ub\"b\" \n"
+        );
+    }
+
+    #[test]
+    fn nested_f_strings() {
+        // quotes can be reused inside of the `{}` since python 3.12, see PEP 701
+        let src = r##"a = f"{x["key"]} {'# not a comment'} {{ {y:>{width}}"
+b = f"""{
+    # a comment
+    ", ".join(items)
+}"""
+"##;
+        let lines = count::get_meaningful_line_indices::<Python>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [0, 1, 3, 4]);
+        assert_eq!(
+            get_cleaned_source_code::<Python>(src).unwrap(),
+            "a = f\"{x[]}{}{{{y:>{width}}\"\nb = f\"\"\"{\n.join(items)\n}\"\"\"\n"
+        );
+    }
+
     #[test]
     fn golden_file_test() {
         // ./src/fixtures/python_tests.py
@@ -430,6 +692,8 @@ mod simple_rust {
 
 #[cfg(test)]
 mod simple_js {
+    use crate::count;
+    use crate::langs::*;
     use crate::parse::v2::get_lines_without_ranges;
     use crate::parse::v2::Parser;
     use crate::parse::v2::TreeSitterLanguage;
@@ -457,6 +721,23 @@ mod simple_js {
         };
         assert_eq!(cnt_executable, 6); // we can now ignore parens and curlies
     }
+
+    #[test]
+    fn try_parse_v1() {
+        let src = r#"const quote = /["'/]+/g; // a regex
+const half = total / 2 / count; // "not a string
+const msg = `it's ${user.map((u) => {
+    // a comment in an interpolation
+    return `${u.name}'s`;
+})}
+still a template // not a comment`;
+if (x) return /`/.test(msg);
+"#;
+        let lines = count::get_meaningful_line_indices::<JS>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [0, 1, 2, 4, 7]);
+    }
 }
 
 #[cfg(test)]
@@ -499,9 +780,12 @@ mod simple_jsx {
 
 #[cfg(test)]
 mod simple_ts {
+    use crate::count;
+    use crate::langs::*;
     use crate::parse::v2::get_lines_without_ranges;
     use crate::parse::v2::Parser;
     use crate::parse::v2::TreeSitterLanguage;
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn try_parse() {
@@ -525,6 +809,1131 @@ mod simple_ts {
             // crate::parse::v2::display_lines(src, &lines);
             lines.len()
         };
-        assert_eq!(cnt_executable, 7); // we can now ignore parens and curlies
+        // we can now ignore parens and curlies
+        assert_eq!(cnt_executable, 7);
+        // lines with only template literal text and `` `; `` aren't meaningful in v1
+        let lines = count::get_meaningful_line_indices::<TypeScript>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [1, 3, 4, 5, 7]);
+    }
+
+    #[test]
+    fn regex_and_decorators() {
+        let src = r#"
+        @Component({
+            selector: "app", // the tag
+        })
+        class App {
+            @Input()
+            pattern = /["'`]+\/*/g; // not a comment or a string
+            half = this.width / 2 / this.scale; // division
+            label = `count: ${this.items.filter((x) => {
+                return /^a/.test(x);
+            }).length}`;
+        }
+        "#;
+        let lines = count::get_meaningful_line_indices::<TypeScript>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [1, 2, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(
+            count::get_cleaned_source_code::<TypeScript>(
+                "let xs: Array<string> = [\"it's\"]; // it's a comment\n"
+            )
+            .unwrap(),
+            "let xs: Array<string> = []; \n"
+        );
+        assert_eq!(
+            count::get_cleaned_source_code::<TypeScript>("x = a / b; // c\ny = /\\//;\n").unwrap(),
+            "x = a / b; \ny = ;\n"
+        );
+    }
+}
+
+#[cfg(test)]
+mod simple_tsx {
+    use crate::count;
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn try_parse() {
+        let src = r#"
+        export const App = ({ name }: Props) => (
+            <div className="app">
+                <p>Don't {name} worry</p>
+                {/* it's a comment */}
+            </div>
+        );
+        "#;
+        let lines = count::get_meaningful_line_indices::<Tsx>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [1, 2, 3, 5]);
+        // in TypeScript the quote in `Don't` begins a string that ends in the comment
+        let lines = count::get_meaningful_line_indices::<TypeScript>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn generics() {
+        let src = r#"const xs: Array<string> = ["it's", "/* no"]; // it's a comment
+const m = new Map<string, Array<number>>(); // comment
+const el = <p>a <b>don't</b> it's</p>;
+return <i>it's</i>;
+"#;
+        // text after a generic isn't JSX text, text after a tag is
+        assert_eq!(
+            count::get_cleaned_source_code::<Tsx>(src).unwrap(),
+            "const xs: Array<string> = [, ]; \nconst m = new Map<string, Array<number>>(); \nconst el = <p>a <b>don't</b> it's</p>;\nreturn <i>it's</i>;\n"
+        );
+    }
+}
+
+#[cfg(test)]
+mod simple_html {
+    use crate::count::*;
+    use crate::langs::*;
+    use crate::parse::get_attribute;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn attributes() {
+        assert_eq!(get_attribute(r#" setup lang="ts""#, "lang"), Some("ts"));
+        assert_eq!(get_attribute(" lang='scss' scoped", "lang"), Some("scss"));
+        assert_eq!(get_attribute(" type=module", "type"), Some("module"));
+        assert_eq!(get_attribute(r#" xml:lang="en""#, "lang"), None);
+    }
+
+    #[test]
+    fn vue_component() {
+        let src = r#"<template>
+  <!-- greeting -->
+  <div class="a > b">{{ msg }}</div>
+</template>
+
+<script setup lang="ts">
+// the message
+const msg: string = "hi";
+</script>
+
+<style lang="scss" scoped>
+// nested rules
+.a {
+  color: red;
+}
+</style>
+"#;
+        assert_eq!(
+            get_meaningful_line_indices_by_language::<Vue>(src, CountPolicy::default()),
+            [
+                ("html", vec![0, 2, 3, 5, 8, 10, 15]),
+                ("typescript", vec![7]),
+                ("scss", vec![12, 13]),
+            ]
+        );
+    }
+
+    #[test]
+    fn vue_component_langs() {
+        let src = r#"<script lang="tsx">
+// it's a comment
+const view = <div>don't "quote" me</div>;
+</script>
+
+<style lang="postcss">
+/* postcss */
+.a { color: red; }
+</style>
+
+<style lang="stylus">
+// stylus
+.b
+  color blue
+</style>
+"#;
+        assert_eq!(
+            get_meaningful_line_indices_by_language::<Vue>(src, CountPolicy::default()),
+            [
+                ("html", vec![0, 3, 5, 8, 10, 14]),
+                ("tsx", vec![2]),
+                ("css", vec![7]),
+                ("scss", vec![12, 13]),
+            ]
+        );
+    }
+
+    #[test]
+    fn html_page() {
+        let src = r#"<html>
+<!-- <script>not a script</script> -->
+<SCRIPT type="application/json">{"a": 1}</SCRIPT>
+<script>
+  /* setup */
+  run();
+</script>
+<style>p { margin: 0; }</style>
+<script src="a.js"></script>
+</html>
+"#;
+        assert_eq!(
+            get_count_of_meaningful_lines_by_language::<Html>(src, CountPolicy::default()),
+            [("html", 7), ("javascript", 1), ("css", 1)]
+        );
+    }
+
+    #[test]
+    fn svelte_component() {
+        let src = r#"<script lang="ts">
+  // the count
+  let count: number = 0;
+</script>
+
+<button on:click={() => count++}>{count}</button>
+"#;
+        assert_eq!(
+            get_meaningful_line_indices_by_language::<Svelte>(src, CountPolicy::default()),
+            [("html", vec![0, 3, 5]), ("typescript", vec![2])]
+        );
+        let src = src.replace(r#"lang="ts""#, r#"type="text/typescript""#);
+        assert_eq!(
+            get_meaningful_line_indices_by_language::<Svelte>(&src, CountPolicy::default()),
+            [("html", vec![0, 3, 5]), ("typescript", vec![2])]
+        );
+    }
+}
+
+#[cfg(test)]
+mod simple_notebook {
+    use crate::count::CountPolicy;
+    use crate::notebook::*;
+    use pretty_assertions::assert_eq;
+
+    const NOTEBOOK: &str = r##"{
+ "cells": [
+  {
+   "cell_type": "markdown",
+   "metadata": {},
+   "source": ["# Title\n", "\n", "Some *docs*"]
+  },
+  {
+   "cell_type": "code",
+   "execution_count": 1,
+   "metadata": {},
+   "outputs": [],
+   "source": ["import numpy as np\n", "# a comment\n", "\n", "x = np.array([\n", "    1,\n", "])"]
+  },
+  {
+   "cell_type": "code",
+   "execution_count": null,
+   "metadata": {},
+   "outputs": [],
+   "source": "\"\"\"doc\"\"\"\nprint(x)"
+  }
+ ],
+ "metadata": {
+  "kernelspec": {"display_name": "Python 3", "language": "python", "name": "python3"}
+ },
+ "nbformat": 4,
+ "nbformat_minor": 5
+}"##;
+
+    #[test]
+    fn cells() {
+        let notebook = get_meaningful_lines_of_notebook(NOTEBOOK, CountPolicy::default()).unwrap();
+        assert_eq!(notebook.language, "python");
+        assert_eq!(
+            notebook
+                .cells
+                .iter()
+                .map(|c| (c.index, c.kind, c.meaningful_lines.clone()))
+                .collect::<Vec<_>>(),
+            [
+                (0, CellKind::Markdown, vec![]),
+                (1, CellKind::Code, vec![0, 3, 4]),
+                (2, CellKind::Code, vec![1]),
+            ]
+        );
+        assert_eq!(notebook.meaningful_lines(), 4);
+        assert_eq!(notebook.documentation_lines(), 2);
+    }
+
+    #[test]
+    fn kernel_language() {
+        let src = NOTEBOOK.replace(r#""language": "python""#, r#""language": "rust""#);
+        let notebook = get_meaningful_lines_of_notebook(&src, CountPolicy::default()).unwrap();
+        assert_eq!(notebook.language, "rust");
+        let src = NOTEBOOK.replace(r#""language": "python""#, r#""language": "brainfuck""#);
+        assert_eq!(
+            get_meaningful_lines_of_notebook(&src, CountPolicy::default()),
+            None
+        );
+        assert_eq!(
+            get_count_of_meaningful_lines_of_notebook("not json", CountPolicy::default()),
+            None
+        );
+    }
+
+    /// A notebook with a single code cell in the given kernel language
+    fn notebook(language: &str, source: &str) -> String {
+        format!(
+            r#"{{
+ "cells": [{{"cell_type": "code", "metadata": {{}}, "outputs": [], "source": {source}}}],
+ "metadata": {{"kernelspec": {{"language": "{language}"}}}},
+ "nbformat": 4,
+ "nbformat_minor": 5
+}}"#
+        )
+    }
+
+    #[test]
+    fn julia_kernel() {
+        let src = notebook(
+            "julia",
+            r##"["#= a\n", "   comment =#\n", "x = A'\n", "println(\"$(x) # not a comment\")\n", "end"]"##,
+        );
+        let notebook = get_meaningful_lines_of_notebook(&src, CountPolicy::default()).unwrap();
+        assert_eq!(notebook.language, "julia");
+        assert_eq!(notebook.cells[0].meaningful_lines, [2, 3]);
+    }
+
+    #[test]
+    fn r_kernel() {
+        let src = notebook(
+            "R",
+            r##"["# a comment\n", "x <- r\"(a \"# b\")\"\n", "print(x)"]"##,
+        );
+        let notebook = get_meaningful_lines_of_notebook(&src, CountPolicy::default()).unwrap();
+        assert_eq!(notebook.language, "r");
+        assert_eq!(notebook.cells[0].meaningful_lines, [1, 2]);
+        let src = src.replace(r#""language": "R""#, r#""language": "ir""#);
+        assert_eq!(
+            get_count_of_meaningful_lines_of_notebook(&src, CountPolicy::default()),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn compiled_kernels() {
+        let src = notebook(
+            "C++17",
+            r##"["// a comment\n", "auto s = R\"(\n// not a comment)\";\n", "}"]"##,
+        );
+        let count = get_meaningful_lines_of_notebook(&src, CountPolicy::default()).unwrap();
+        assert_eq!(count.language, "cpp");
+        assert_eq!(count.cells[0].meaningful_lines, [1]);
+        for language in ["go", "kotlin", "typescript"] {
+            let src = notebook(
+                language,
+                r#"["// a comment\n", "x := f(\"// no\")\n", "}"]"#,
+            );
+            let count = get_meaningful_lines_of_notebook(&src, CountPolicy::default()).unwrap();
+            assert_eq!(count.language, language);
+            assert_eq!(count.cells[0].meaningful_lines, [1]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod simple_go {
+    use crate::count;
+    use crate::langs::*;
+    use crate::parse::v2::get_lines_without_ranges;
+    use crate::parse::v2::Parser;
+    use crate::parse::v2::TreeSitterLanguage;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn try_parse() {
+        let src = r#"
+        package main
+
+        /* imports
+           go here */
+        import "fmt"
+
+        func main() {
+            // quotes in runes aren't strings
+            q := '"'
+            s := "escaped \" // not a comment"
+            r := `raw \
+                // still the string
+            `
+            fmt.Println(q, s, r)
+        }
+        "#;
+        let lines = count::get_meaningful_line_indices::<Go>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [1, 5, 7, 9, 10, 11, 14]);
+        let cnt_executable = {
+            let mut parser = Parser::new(TreeSitterLanguage::Go).unwrap();
+            let ranges = parser.non_executable_src_spans(src).unwrap();
+            let lines = get_lines_without_ranges(src, ranges);
+            // crate::parse::v2::display_lines(src, &lines);
+            lines.len()
+        };
+        assert_eq!(cnt_executable, 7);
+    }
+
+    #[test]
+    fn cleaned_source() {
+        let src = "x := `a\n// b`\ny := '\\'' // c\n";
+        assert_eq!(
+            count::get_cleaned_source_code::<Go>(src).unwrap(),
+            "x := \ny := \n"
+        );
+    }
+}
+
+#[cfg(test)]
+mod simple_kotlin {
+    use crate::count;
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn try_parse() {
+        let src = r#"
+        /* outer /* nested */
+           still a comment */
+        fun main() {
+            val c = '"'
+            val name = "kotlin // not a comment"
+            val greeting = """
+                hello,
+                ${name.map {
+                    it.uppercase()
+                }}
+                and $name
+            """
+            println("$greeting, \${escaped}")
+        }
+        "#;
+        let lines = count::get_meaningful_line_indices::<Kotlin>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [3, 4, 5, 6, 8, 9, 11, 13]);
+    }
+
+    #[test]
+    fn cleaned_source() {
+        let src = "val s = \"a ${x + 1} b $y\" /* c /* d */ */\n";
+        assert_eq!(
+            count::get_cleaned_source_code::<Kotlin>(src).unwrap(),
+            "val s = ${x + 1}$y\n"
+        );
+    }
+}
+
+#[cfg(test)]
+mod simple_swift {
+    use crate::count;
+    use crate::langs::*;
+    use crate::parse::v2::get_lines_without_ranges;
+    use crate::parse::v2::Parser;
+    use crate::parse::v2::TreeSitterLanguage;
+    use pretty_assertions::assert_eq;
+
+    const SRC: &str = r####"
+        /* outer /* nested */
+           still a comment */
+        func greet(_ name: String) -> String {
+            let raw = #"no \(interpolation) "here" "#
+            let block = ##"""
+                """# is still the string
+                """##
+            let text = """
+                hello,
+                \(name.map {
+                    $0.uppercased()
+                })
+                """
+            return "\(text) // not a comment"
+        }
+        "####;
+
+    #[test]
+    fn try_parse() {
+        let lines = count::get_meaningful_line_indices::<Swift>(SRC)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [3, 4, 5, 8, 10, 11, 14]);
+    }
+
+    #[test]
+    fn try_parse_v2() {
+        let mut parser = Parser::new(TreeSitterLanguage::Swift).unwrap();
+        let ranges = parser.non_executable_src_spans(SRC).unwrap();
+        let lines = get_lines_without_ranges(SRC, ranges);
+        // line indices start from 1 here, interpolations are a part of the string
+        assert_eq!(lines, [4, 5, 6, 9, 15]);
+    }
+
+    #[test]
+    fn extended_interpolation() {
+        let src = r####"let s = #"""
+    \#(
+        value
+    )
+    \(not.source) \##(nor.this)
+    """#
+let t = ##"\#(no) \##(yes)"##
+"####;
+        let lines = count::get_meaningful_line_indices::<Swift>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [0, 1, 2, 6]);
+        assert_eq!(
+            count::get_cleaned_source_code::<Swift>(src).unwrap(),
+            "let s = \\#(\n        value\nlet t = \\##(yes)\n"
+        );
+    }
+}
+
+#[cfg(test)]
+mod simple_php {
+    use crate::count::*;
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    const SRC: &str = r#"<h1>Don't count me</h1>
+<?php
+# a comment
+#[Attribute]
+class Greeter {
+    // ends at ?> <b>inline</b> <?php
+    public function greet($name) {
+        $text = <<<EOT
+            hello
+            EOT;
+        return "$name {$this->x}
+        // not a comment";
+    }
+}
+?>
+<p>bye</p>
+"#;
+
+    #[test]
+    fn try_parse() {
+        let lines = get_meaningful_line_indices::<Php>(SRC)
+            .flatten()
+            .collect::<Vec<_>>();
+        // the inline html isn't meaningful as php
+        assert_eq!(lines, [3, 4, 6, 7, 10]);
+    }
+
+    #[test]
+    fn inline_html() {
+        assert_eq!(
+            get_count_of_meaningful_lines_by_language::<Php>(SRC, CountPolicy::default()),
+            [("html", 3), ("php", 5)]
+        );
+    }
+}
+
+#[cfg(test)]
+mod simple_shell {
+    use crate::count;
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    const SRC: &str = r#"#!/bin/sh
+# usage: build.sh [target]
+echo "args: $# ${#1}" # counted
+name=${1#prefix}
+if [ -z "$(echo "$name" | tr -d '#')" ]; then
+    echo 'no \escapes here'
+fi
+cat <<-'EOF' | grep x
+	echo "this is text" # not a comment
+	EOF
+printf $'tab\t\'quoted\'\n'
+echo don\'t "quote
+# not a comment
+"
+"#;
+
+    #[test]
+    fn try_parse() {
+        let lines = count::get_meaningful_line_indices::<Shell>(SRC)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [2, 3, 4, 5, 7, 10, 11]);
+    }
+
+    #[test]
+    fn heredocs() {
+        let src = "cat <<EOF | grep x # comment\n\tEOF\nEOF\ncat <<-'EOF'\n\ttext\n\tEOF\necho done\n<<EOF cat\ntext\nEOF\n";
+        // only `<<-` heredocs end on a line indented with tabs
+        assert_eq!(
+            count::get_meaningful_line_indices::<Shell>(src)
+                .flatten()
+                .collect::<Vec<_>>(),
+            [0, 3, 6, 7]
+        );
+        // the rest of the line a heredoc begins on is source
+        assert_eq!(
+            count::get_cleaned_source_code::<Shell>(src).unwrap(),
+            "cat  | grep x \ncat \necho done\n cat\n"
+        );
+    }
+}
+
+#[cfg(test)]
+mod simple_lua {
+    use crate::count;
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    const SRC: &str = r#"--[==[
+  a comment with ]] and ]=] in it
+]==]
+local t = { "a\"", 'b' } -- comment
+local s = [[
+line -- not a comment
+]]
+local nested = [=[ ]] ]=] print(t[1])
+function f(x)
+  return x
+end
+"#;
+
+    #[test]
+    fn try_parse() {
+        let lines = count::get_meaningful_line_indices::<Lua>(SRC)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [3, 4, 7, 8, 9]);
+    }
+}
+
+#[cfg(test)]
+mod simple_sql {
+    use crate::count;
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn standard() {
+        let src = r#"-- create the table
+CREATE TABLE "my--table" (
+    name TEXT DEFAULT 'it''s -- not a comment',
+    /* a
+       block */
+    id INTEGER
+);
+"#;
+        let lines = count::get_meaningful_line_indices::<Sql>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [1, 2, 5]);
+    }
+
+    #[test]
+    fn postgres() {
+        let src = r#"/* outer /* nested */
+   still a comment */
+CREATE FUNCTION f(a int) RETURNS text AS $body$
+BEGIN
+    RETURN $$it's$$ || E'\'' || $1;
+END;
+$body$ LANGUAGE plpgsql;
+"#;
+        let lines = count::get_meaningful_line_indices::<Postgres>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn postgres_dollar_quoted_bodies() {
+        // `$$` inside of `$fn$` doesn't end it
+        let src = "AS $fn$\n  s := $$don't$$;\n$fn$;\n-- comment 1\nSELECT 'x';";
+        let lines = count::get_meaningful_line_indices::<Postgres>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [0, 1, 2, 4]);
+        // comments in the body aren't source, nor are the comment markers in its strings
+        let src = r#"AS $$
+BEGIN
+  -- just a comment
+  /* block /* nested */ */
+  RETURN '-- not a comment' || E'\'/*' || "a--b";
+END;
+$$;
+"#;
+        let lines = count::get_meaningful_line_indices::<Postgres>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [0, 1, 4, 5, 6]);
+        // unterminated bodies and inner quotes run to the end of the source
+        for (src, expected) in [
+            ("select $$s$\"$sE", vec![0]),
+            ("AS $fn$\nBEGIN\n  -- c\n  x := $a$", vec![0, 1, 3]),
+        ] {
+            let lines = count::get_meaningful_line_indices::<Postgres>(src)
+                .flatten()
+                .collect::<Vec<_>>();
+            assert_eq!(lines, expected);
+        }
+    }
+
+    #[test]
+    fn mysql() {
+        let src = r#"# a comment
+SELECT `it's`, 'a\'b', "c\"d" FROM t; -- comment
+SELECT 1--1;
+"#;
+        let lines = count::get_meaningful_line_indices::<MySql>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [1, 2]);
+    }
+
+    #[test]
+    fn tsql() {
+        let src = r#"SELECT [it's a -- column]
+/* outer /* nested */ */
+FROM t
+"#;
+        let lines = count::get_meaningful_line_indices::<TSql>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [0, 2]);
+    }
+}
+
+#[cfg(test)]
+mod simple_haskell {
+    use crate::count::*;
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    const SRC: &str = r#"{-# LANGUAGE OverloadedStrings #-}
+{- outer {- nested -}
+   still a comment -}
+module Main where
+-- | a haddock comment, `--|` would be an operator
+x --> y = x |-- y
+main = do
+  let x' = foldl' (+) 0 [1, 2] -- it's a comment
+      c = '\'' : 'a' : "-- not a comment"
+  print (x', c)
+"#;
+
+    #[test]
+    fn try_parse() {
+        let lines = get_meaningful_line_indices::<Haskell>(SRC)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [0, 3, 5, 6, 7, 8, 9]);
+        // pragmas are never executable
+        let lines = get_meaningful_line_indices::<Haskell>(SRC)
+            .with_policy(CountPolicy::EXECUTABLE)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [3, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn non_ascii_escapes() {
+        let lines = get_meaningful_line_indices::<Haskell>("c = '\\λ'\n-- '\\λ'\nd = '\\λx'")
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [0, 2]);
+    }
+}
+
+#[cfg(test)]
+mod simple_ruby {
+    use crate::count;
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    const SRC: &str = r##"=begin
+a block comment at the start of the file
+=end
+puts "# not a comment #{"nested } string" + x} still a string"
+words = %w(a # b
+  c)
+quoted = %q|it's # here|
+keyed = %Q{#{ %q<a> }
+}
+sym = :"# symbol"
+x = 10 %(3) # a comment
+y = 7 % 3
+`echo '#{y}'`
+__END__
+puts "this is data"
+"##;
+
+    #[test]
+    fn try_parse() {
+        let lines = count::get_meaningful_line_indices::<Ruby>(SRC)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [3, 4, 6, 7, 9, 10, 11, 12]);
+    }
+
+    #[test]
+    fn percent_literals() {
+        let lines = |src| {
+            count::get_meaningful_line_indices::<Ruby>(src)
+                .flatten()
+                .collect::<Vec<_>>()
+        };
+        // a modulo assignment isn't a literal delimited by `=`
+        assert_eq!(
+            lines("i = 10\ni %= 3\nputs i\n# comment =\nputs 2"),
+            [0, 1, 2, 4]
+        );
+        assert_eq!(lines("x = %=a b=\n# comment ="), [0]);
+        // `%q` doesn't interpolate, so `#{` is a part of it
+        assert_eq!(lines("x = %q{#{\n}\n# c\ny = 1"), [0, 3]);
+        assert_eq!(
+            count::get_cleaned_source_code::<Ruby>("w = %w(a (b) c) + %Q[#{[x]} [y]]\n").unwrap(),
+            "w =  + #{[x]}\n"
+        );
+        // brackets stay nested around interpolation
+        assert_eq!(
+            count::get_cleaned_source_code::<Ruby>("w = %W(a (#{b}) c) + 1\n").unwrap(),
+            "w = #{b} + 1\n"
+        );
+    }
+}
+
+#[cfg(test)]
+mod simple_csharp {
+    use crate::count;
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn try_parse() {
+        let src = r#"var path = @"C:\dir\";
+var quoted = @"say ""hi"" // not a comment
+    still a string";
+var name = $"{(ok ? "a" : "b")} {{ // not a comment";
+var both = $@"{path}\""{x}""
+    {
+        // an interpolation
+        x
+    }";
+var raw = """
+    "quotes" and "" and \ // not a comment
+    """;
+var json = $$"""
+    { "a": {{x}} }
+    """;
+char c = '"', d = '\'';
+"#;
+        let lines = count::get_meaningful_line_indices::<Csharp>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [0, 1, 3, 4, 7, 9, 12, 15]);
+    }
+
+    #[test]
+    fn cleaned_source() {
+        let src = "var a = $\"x{b + \"}\"}\" + @\"\\\" + 'c';\n";
+        assert_eq!(
+            count::get_cleaned_source_code::<Csharp>(src).unwrap(),
+            "var a = {b + } +  + ;\n"
+        );
+    }
+}
+
+#[cfg(test)]
+mod simple_scala {
+    use crate::count;
+    use crate::langs::*;
+    use crate::parse::v2::get_lines_without_ranges;
+    use crate::parse::v2::Parser;
+    use crate::parse::v2::TreeSitterLanguage;
+    use pretty_assertions::assert_eq;
+
+    const SRC: &str = r#"/* outer /* nested */
+   still a comment */
+object Main {
+  val doc = """a "quoted" // not a comment
+    text""""
+  val name = s"${user.map { u => "}" }} costs $$5 and $price // not a comment"
+  val json = json"""{"a": $x,
+    "b": 1}"""
+  val c = '"'
+  val sym = 'sym
+  def f(x: Int) = x // a comment
+}
+"#;
+
+    #[test]
+    fn try_parse() {
+        let lines = count::get_meaningful_line_indices::<Scala>(SRC)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [2, 3, 5, 6, 8, 9, 10]);
+    }
+
+    #[test]
+    fn non_ascii_escapes() {
+        let lines = count::get_meaningful_line_indices::<Scala>("val c = '\\λ'\n// '\\λ'")
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [0]);
+    }
+
+    #[test]
+    fn try_parse_v2() {
+        let mut parser = Parser::new(TreeSitterLanguage::Scala).unwrap();
+        let ranges = parser.non_executable_src_spans(SRC).unwrap();
+        let lines = get_lines_without_ranges(SRC, ranges);
+        // line indices start from 1 here, and the comment is never executable
+        assert!(!lines.contains(&1) && !lines.contains(&2));
+        assert!(lines.contains(&11));
+    }
+}
+
+#[cfg(test)]
+mod simple_java {
+    use crate::count::{self, get_cleaned_source_code};
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn try_parse() {
+        let src = r#"String s = "\\"; // the string ends at the second quote
+char q = '"', a = '\'';
+String t = """
+    \""" still in the block
+    """;
+// \u000a int hidden = 1;
+String e = "\u0022\u0022"; // two empty strings
+"#;
+        let lines = count::get_meaningful_line_indices::<Java>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [0, 1, 2, 5, 6]);
+    }
+
+    #[test]
+    fn golden_file_test() {
+        let src = std::fs::read_to_string("./src/fixtures/java_tests.java").unwrap();
+        let golden_src = std::fs::read_to_string("./src/fixtures/java_tests_golden.java").unwrap();
+        assert_eq!(get_cleaned_source_code::<Java>(&src).unwrap(), golden_src);
+    }
+}
+
+#[cfg(test)]
+mod simple_dart {
+    use crate::count;
+    use crate::langs::*;
+    use crate::parse::v2::get_lines_without_ranges;
+    use crate::parse::v2::Parser;
+    use crate::parse::v2::TreeSitterLanguage;
+    use pretty_assertions::assert_eq;
+
+    const SRC: &str = r#"/// Greets people.
+/* outer /* nested */
+   still a comment */
+String greet(String name) {
+  final raw = r'no $interpolation \n here';
+  final text = '''
+    hello, ${name.toUpperCase()}
+    ''';
+  return "$text // not a comment";
+}
+"#;
+
+    #[test]
+    fn try_parse() {
+        let lines = count::get_meaningful_line_indices::<Dart>(SRC)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [3, 4, 5, 6, 8]);
+    }
+
+    #[test]
+    fn try_parse_v2() {
+        let mut parser = Parser::new(TreeSitterLanguage::Dart).unwrap();
+        let ranges = parser.non_executable_src_spans(SRC).unwrap();
+        let lines = get_lines_without_ranges(SRC, ranges);
+        // line indices start from 1 here, and the comments are never executable
+        assert!(!lines.contains(&1) && !lines.contains(&2) && !lines.contains(&3));
+        assert!(lines.contains(&9));
+    }
+}
+
+#[cfg(test)]
+mod simple_elixir {
+    use crate::count;
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn try_parse() {
+        let src = r#"defmodule Greeter do
+  @moduledoc """
+  Greets "people", #{not code}
+  """
+  # a comment
+  @pattern ~r/#[a-z]+"/i
+  def greet(name) when name != ?" do
+    ~S(not #{interpolated}) <> "hi #{name} # not a comment"
+  end
+  def valid?(x), do: x
+end
+"#;
+        let lines = count::get_meaningful_line_indices::<Elixir>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [0, 5, 6, 7, 9]);
+    }
+}
+
+#[cfg(test)]
+mod simple_erlang {
+    use crate::count;
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn try_parse() {
+        let src = r#"-module(greeter).
+%% @doc Greets people.
+greet(Name) ->
+    Quote = $", Percent = $%, % a comment
+    io:format("~s % not a comment~n", [Name]),
+    '%atom'.
+"#;
+        let lines = count::get_meaningful_line_indices::<Erlang>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [0, 2, 3, 4, 5]);
+    }
+}
+
+#[cfg(test)]
+mod simple_perl {
+    use crate::count;
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn try_parse() {
+        let src = r#"#!/usr/bin/perl
+use strict; # pragmas
+my @list = qw(a b # not a comment
+    c);
+my $last = $#list;
+(my $s = $text) =~ s{#}{/}g;
+print <<"EOT";
+    hello # still the heredoc
+EOT
+
+=head1 NAME
+
+greet - # not code
+
+=cut
+
+print m/"/ ? 'quote' : q|none|;
+__END__
+print "data";
+"#;
+        let lines = count::get_meaningful_line_indices::<Perl>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [1, 2, 4, 5, 6, 16]);
+    }
+
+    #[test]
+    fn bare_matches() {
+        let lines = |src| {
+            count::get_meaningful_line_indices::<Perl>(src)
+                .flatten()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            lines("if ($x =~ /\"/) {\n  foo();\n  baz();\n}\n# c\nbar(\"a\");"),
+            [0, 1, 2, 5]
+        );
+        assert_eq!(
+            lines("my @a = split /'/, $x;\nfoo();\n# c\nbar('a');"),
+            [0, 1, 3]
+        );
+        // divisions aren't matches
+        assert_eq!(
+            count::get_cleaned_source_code::<Perl>("my $y = $h{a} / 2 / $b; # c\n").unwrap(),
+            "my $y = $h{a} / 2 / $b; \n"
+        );
+        assert_eq!(
+            count::get_cleaned_source_code::<Perl>("my @w = qw(a (b) c) if $x !~ m{a{2}};\n")
+                .unwrap(),
+            "my @w =  if $x !~ ;\n"
+        );
+    }
+}
+
+#[cfg(test)]
+mod simple_julia {
+    use crate::count;
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn try_parse() {
+        let src = r#"#= outer #= nested =#
+   still a comment =#
+function greet(name)
+    # a comment
+    text = """
+        hello, $(uppercase(name))
+        """
+    c = '"'; t = x'
+    return "$text # not a comment"
+end
+"#;
+        let lines = count::get_meaningful_line_indices::<Julia>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [2, 4, 5, 7, 8]);
+    }
+
+    #[test]
+    fn non_ascii_escapes() {
+        let lines = count::get_meaningful_line_indices::<Julia>("c = '\\λ'\n# '\\λ'")
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [0]);
+    }
+}
+
+#[cfg(test)]
+mod simple_r {
+    use crate::count;
+    use crate::langs::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn try_parse() {
+        let src = r##"# a comment
+`my var` <- r"-(C:\path)"# not)-"
+x <- "hash # inside" # trailing
+raw <- R"[
+  multi # line
+]"
+f <- function(x) {
+  x
+}
+"##;
+        let lines = count::get_meaningful_line_indices::<R>(src)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [1, 2, 3, 6, 7]);
     }
 }